@@ -0,0 +1,76 @@
+//! Compares day1's `TokenScanner` and `BidirectionalSearch` with the
+//! `combined_parser` tokeniser on a large generated input.
+//! Run with `cargo bench --bench day1_scanner`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use advent_of_code::day1::{
+    tokenised_calibration_value, BidirectionalSearch, DigitVocabulary, TokenScanner,
+};
+
+// The bench has no test harness, so the tests in testing.rs never use their
+// imports when it is built with `--all-targets`.
+#[path = "../src/testing.rs"]
+#[allow(unused_imports)]
+mod testing;
+
+use testing::XorShift;
+
+const LINES: usize = 200_000;
+const ROUNDS: usize = 5;
+
+fn generate(vocabulary: &DigitVocabulary) -> Vec<String> {
+    let mut rng = XorShift::new(0x2023_1201);
+    let words = vocabulary.words();
+
+    (0..LINES)
+        .map(|_| {
+            let mut line = String::new();
+            while line.len() < 20 + rng.below(40) {
+                match rng.below(10) {
+                    0 => line.push(char::from(b'0' + rng.below(10) as u8)),
+                    1 | 2 => line.push_str(&words[rng.below(words.len())].0),
+                    _ => line.push(char::from(b'a' + rng.below(26) as u8)),
+                }
+            }
+            line.push('7');
+            line
+        })
+        .collect()
+}
+
+fn time(name: &str, lines: &[String], f: impl Fn(&str) -> Option<u32>) -> u64 {
+    let mut best = Duration::MAX;
+    let mut total = 0;
+
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        total = lines
+            .iter()
+            .map(|l| black_box(f(l)).unwrap_or(0) as u64)
+            .sum();
+        best = best.min(start.elapsed());
+    }
+
+    let bytes: usize = lines.iter().map(String::len).sum();
+    let throughput = bytes as f64 / best.as_secs_f64() / (1024.0 * 1024.0);
+    println!("{:<10} {:>10.2?} {:>8.1} MiB/s", name, best, throughput);
+    total
+}
+
+fn main() {
+    let vocabulary = DigitVocabulary::english();
+    let lines = generate(&vocabulary);
+    let scanner = TokenScanner::new(&vocabulary);
+    let search = BidirectionalSearch::new(&vocabulary);
+
+    println!("{} lines, best of {} rounds", LINES, ROUNDS);
+    let tokenised = time("tokenised", &lines, |l| {
+        tokenised_calibration_value(l, &vocabulary)
+    });
+    let scanned = time("scanner", &lines, |l| scanner.calibration_value(l));
+    let searched = time("bidirect", &lines, |l| search.calibration_value(l));
+    assert_eq!(tokenised, scanned, "implementations disagree");
+    assert_eq!(tokenised, searched, "implementations disagree");
+}
@@ -0,0 +1,424 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::process;
+
+use advent_of_code::day1::{self, Day1};
+use advent_of_code::day2::{self, Day2, GameData};
+use advent_of_code::day3::{self, AdjacencyIndex, Schematic};
+use advent_of_code::input::{required, InputError, Source};
+use advent_of_code::registry::{self, Day, DAYS};
+use advent_of_code::solution::{Options, Solution};
+
+const USAGE: &str =
+    "usage: aoc [run] --day <N> [--part <P>] [--input <PATH>|-] [--<option> <VALUE>...]
+       aoc run --all [--inputs <DIR>] [--<option> <VALUE>...]   (any day's options)
+       aoc render [--html] [--input <PATH>|-]   (day 3 schematic)
+       aoc explain [--input <PATH>|-] [--vocabulary <V>]   (day 1 tokens per line)
+       aoc --format [--input <PATH>|-] [--in-place]   (day 2 games, canonical form)
+       aoc query --where <EXPR> [--input <PATH>|-]    (ids of matching day 2 games)
+       aoc infer [--input <PATH>|-] [--max-cubes <N>] (most likely day 2 bags)
+
+day 1 options:
+  --vocabulary <english|german|french|word=value,...>
+  --search <tokenise|scan|bidirectional>
+  --grammar <digits|numbers>   (numbers: English 0-999 such as twenty-one,
+                               without --vocabulary or --search)
+  --combine <digits|numbers>   (join first and last numbers, not digits)
+  --no-digits <error|skip|zero>   (lines without a digit; skip lists them)
+
+day 2 options:
+  --bag <colour=count,...>   (default red=12,green=13,blue=14)
+  --bag-file <PATH>          (one colour = count per line)
+  --parse <strict|lenient>   (lenient skips and lists malformed games)
+  --repeats <error|sum|max>  (a colour named twice in one pick)";
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+enum Command {
+    #[default]
+    Run,
+    Render,
+    Explain,
+    Format,
+    Query,
+    Infer,
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    command: Command,
+    day: Option<u32>,
+    part: Option<usize>,
+    input: Option<String>,
+    inputs: Option<String>,
+    all: bool,
+    html: bool,
+    in_place: bool,
+    options: Options,
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = run(args) {
+        eprintln!("error: {}", e);
+        if let Some(InputError::MissingArgument(_)) = e.downcast_ref::<InputError>() {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+        process::exit(1);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut first = true;
+
+    while let Some(arg) = args.next() {
+        let is_first = first;
+        first = false;
+        match arg.as_str() {
+            "run" if is_first => parsed.command = Command::Run,
+            "render" if is_first => parsed.command = Command::Render,
+            "explain" if is_first => parsed.command = Command::Explain,
+            "query" if is_first => parsed.command = Command::Query,
+            "infer" if is_first => parsed.command = Command::Infer,
+            "--all" => parsed.all = true,
+            "--html" => parsed.html = true,
+            "--format" => parsed.command = Command::Format,
+            "--in-place" => parsed.in_place = true,
+            "--day" => parsed.day = Some(parse_number(&arg, args.next())?),
+            "--part" => parsed.part = Some(parse_number(&arg, args.next())?),
+            "--input" => parsed.input = Some(flag_value(&arg, args.next())?),
+            "--inputs" => parsed.inputs = Some(flag_value(&arg, args.next())?),
+            _ => match arg.strip_prefix("--") {
+                Some(name) if !name.is_empty() => {
+                    parsed.options.set(name, flag_value(&arg, args.next())?)
+                }
+                _ => return Err(format!("unexpected argument '{}'", arg)),
+            },
+        }
+    }
+
+    parsed
+        .options
+        .check(&accepted_options(&parsed))
+        .map_err(|e| e.to_string())?;
+    Ok(parsed)
+}
+
+/// The `--<option>` names the command will read: the options of the day or
+/// days it runs, plus any of its own.
+fn accepted_options(args: &Args) -> Vec<&'static str> {
+    let mut names = match args.command {
+        Command::Run if args.all => DAYS.iter().flat_map(|d| d.options).copied().collect(),
+        Command::Run => args
+            .day
+            .and_then(registry::find)
+            .map_or_else(Vec::new, |d| d.options.to_vec()),
+        Command::Render => vec![],
+        // Explanations show digit tokens, whatever the search or grammar.
+        Command::Explain => vec!["vocabulary"],
+        Command::Format | Command::Query | Command::Infer => Day2::OPTIONS.to_vec(),
+    };
+    match args.command {
+        Command::Query => names.push("where"),
+        Command::Infer => names.push("max-cubes"),
+        _ => {}
+    }
+    names
+}
+
+/// The value after `flag`, which must not itself look like a flag.
+fn flag_value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value
+        .filter(|value| !value.starts_with("--"))
+        .ok_or_else(|| format!("{} requires a value", flag))
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = flag_value(flag, value)?;
+    value
+        .parse::<T>()
+        .map_err(|_| format!("{} expects a number, got '{}'", flag, value))
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        Command::Render => return render(&args),
+        Command::Explain => return explain(&args),
+        Command::Format => return format(&args),
+        Command::Query => return query(&args),
+        Command::Infer => return infer(&args),
+        Command::Run => {}
+    }
+
+    if args.all {
+        let dir = args.inputs.as_deref().unwrap_or("inputs");
+        return run_all(dir, &args.options);
+    }
+
+    let number = required(args.day, "--day (or --all)")?;
+    let day = registry::find(number).ok_or_else(|| format!("day {} is not registered", number))?;
+    run_day(
+        day,
+        args.part,
+        &Source::from_arg(args.input.as_deref()),
+        &args.options,
+    )
+}
+
+/// Runs every registered day from `dir`, carrying on past failures so that
+/// each one is reported.
+fn run_all(dir: &str, options: &Options) -> Result<(), Box<dyn Error>> {
+    let mut failed = 0;
+    for day in DAYS {
+        let path = format!("{}/day{}.txt", dir, day.day);
+        if let Err(e) = run_day(day, None, &Source::from_arg(Some(&path)), options) {
+            failed += 1;
+            eprintln!("error: {}", e);
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} of {} day(s) failed", failed, DAYS.len()).into());
+    }
+    Ok(())
+}
+
+fn run_day(
+    day: &Day,
+    part: Option<usize>,
+    source: &Source,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let input = source.read()?;
+
+    let parts: Vec<usize> = match part {
+        Some(p) => vec![p],
+        None => (1..=day.parts).collect(),
+    };
+
+    let report = (day.solution)(options)?
+        .run(&input, &parts)
+        .map_err(|e| format!("day {}: cannot parse input: {}", day.day, e))?;
+    println!("Day {} parsed in {:?}", day.day, report.parse_elapsed);
+    for note in &report.skipped {
+        eprintln!("Day {} skipped: {}", day.day, note);
+    }
+
+    let mut failed = false;
+    for answer in report.answers {
+        match answer.result {
+            Ok(value) => println!(
+                "Day {} part {}: {} ({:?})",
+                day.day, answer.part, value, answer.elapsed
+            ),
+            Err(e) => {
+                failed = true;
+                eprintln!("Day {} part {}: error: {}", day.day, answer.part, e);
+            }
+        }
+    }
+
+    if failed {
+        return Err(format!("day {} did not solve cleanly", day.day).into());
+    }
+    Ok(())
+}
+
+fn render(args: &Args) -> Result<(), Box<dyn Error>> {
+    let input = Source::from_arg(args.input.as_deref()).read()?;
+    let config = day3::SchematicConfig::default();
+    let schematic = Schematic::parse(&input, &config)?;
+    let index = AdjacencyIndex::build(&schematic, config.radius);
+
+    if args.html {
+        print!("{}", day3::render_html(&index));
+    } else {
+        print!("{}", day3::render_ansi(&index));
+    }
+    Ok(())
+}
+
+fn explain(args: &Args) -> Result<(), Box<dyn Error>> {
+    let input = Source::from_arg(args.input.as_deref()).read()?;
+    let day = Day1::from_options(&args.options)?;
+
+    for (index, line) in input.lines().enumerate() {
+        print!("{}: {}", index + 1, day1::explain(line, day.vocabulary()));
+    }
+    Ok(())
+}
+
+fn format(args: &Args) -> Result<(), Box<dyn Error>> {
+    let source = Source::from_arg(args.input.as_deref());
+    if let (Source::Stdin, true) = (&source, args.in_place) {
+        return Err("--in-place needs --input <PATH>".into());
+    }
+    let (games, skipped) = parse_games(args, &source.read()?)?;
+    let formatted = day2::format_games(&games);
+
+    match &source {
+        Source::File(path) if args.in_place && skipped > 0 => {
+            return Err(format!(
+                "not rewriting {}: {} line(s) could not be parsed",
+                path.display(),
+                skipped
+            )
+            .into())
+        }
+        Source::File(path) if args.in_place => fs::write(path, formatted)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?,
+        _ => print!("{}", formatted),
+    }
+    Ok(())
+}
+
+/// Parses day 2 games under the command-line options, listing any lines the
+/// parse passed over on stderr and returning how many there were.
+fn parse_games(args: &Args, input: &str) -> Result<(Vec<GameData>, usize), Box<dyn Error>> {
+    let (games, skipped) = Day2::from_options(&args.options)?.parse_with_skipped(input)?;
+    for note in &skipped {
+        eprintln!("skipped: {}", note);
+    }
+    Ok((games, skipped.len()))
+}
+
+fn query(args: &Args) -> Result<(), Box<dyn Error>> {
+    let query: day2::Query = required(args.options.get("where"), "--where")?.parse()?;
+    let input = Source::from_arg(args.input.as_deref()).read()?;
+    let (games, _) = parse_games(args, &input)?;
+
+    for id in day2::select(&games, &query) {
+        println!("{}", id);
+    }
+    Ok(())
+}
+
+fn infer(args: &Args) -> Result<(), Box<dyn Error>> {
+    let limit = args
+        .options
+        .parse("max-cubes")?
+        .unwrap_or(day2::DEFAULT_LIMIT);
+    let input = Source::from_arg(args.input.as_deref()).read()?;
+    let (games, _) = parse_games(args, &input)?;
+    let inference = day2::infer(&games, limit)?;
+    let describe = |estimate: &day2::Estimate| {
+        let bounded = estimate.bag.counts().any(|(_, n)| n == limit);
+        format!(
+            "{} (log-likelihood {:.3}){}",
+            estimate.bag,
+            estimate.log_likelihood,
+            if bounded { ", at --max-cubes" } else { "" }
+        )
+    };
+
+    for (id, estimate) in &inference.games {
+        println!("Game {}: {}", id, describe(estimate));
+    }
+    if let Some(overall) = &inference.overall {
+        println!("All games: {}", describe(overall));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod aoc_tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_unknown_options_are_rejected() {
+        assert!(args(&["--day", "1", "--search", "scan"]).is_ok());
+        assert!(args(&["query", "--where", "id > 1", "--parse", "lenient"]).is_ok());
+        assert!(args(&["run", "--all", "--bag", "red=1", "--search", "scan"]).is_ok());
+
+        assert_eq!(
+            args(&["--day", "1", "--serch", "scan"]).unwrap_err(),
+            "unknown option --serch"
+        );
+        assert_eq!(
+            args(&["--day", "2", "--search", "scan"]).unwrap_err(),
+            "unknown option --search"
+        );
+        assert_eq!(
+            args(&["render", "--max-cubes", "3"]).unwrap_err(),
+            "unknown option --max-cubes"
+        );
+        assert_eq!(
+            args(&["explain", "--grammar", "numbers"]).unwrap_err(),
+            "unknown option --grammar"
+        );
+        assert_eq!(
+            args(&["--day", "1", "--search", "--part", "2"]).unwrap_err(),
+            "--search requires a value"
+        );
+    }
+
+    #[test]
+    fn test_run_all_reports_every_failing_day() {
+        let dir = env::temp_dir().join(format!("aoc-run-all-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day1.txt"), "1abc2\n").unwrap();
+
+        let error = run_all(&dir.display().to_string(), &Options::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("{} of {} day(s) failed", DAYS.len() - 1, DAYS.len())
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_in_place_without_input_does_not_read_stdin() {
+        let args = Args {
+            command: Command::Format,
+            in_place: true,
+            ..Args::default()
+        };
+        assert_eq!(
+            format(&args).unwrap_err().to_string(),
+            "--in-place needs --input <PATH>"
+        );
+    }
+
+    #[test]
+    fn test_format_in_place_keeps_files_with_skipped_lines() {
+        let path = env::temp_dir().join(format!("aoc-format-{}.txt", process::id()));
+        let original = "Game 1: 4 red,  3 blue\nGame 2: 4 red,\n";
+        fs::write(&path, original).unwrap();
+
+        let mut args = Args {
+            command: Command::Format,
+            input: Some(path.display().to_string()),
+            in_place: true,
+            ..Args::default()
+        };
+        args.options.set("parse", "lenient");
+        let error = format(&args).unwrap_err().to_string();
+        assert!(
+            error.ends_with("1 line(s) could not be parsed"),
+            "{}",
+            error
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        fs::write(&path, "Game 1: 4 red,  3 blue\n").unwrap();
+        format(&args).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "Game 1: 3 blue, 4 red\n"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,772 @@
+use nom::branch::alt;
+use nom::combinator::eof;
+use nom::multi::many_till;
+use nom::IResult;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::solution::{Options, Solution, SolutionResult};
+
+mod explain;
+mod numbers;
+mod scanner;
+mod vocabulary;
+
+pub use explain::{explain, ExplainedToken, Explanation};
+pub use numbers::{number_calibration_value, numbers, Combine};
+pub use scanner::{BidirectionalSearch, Matches, TokenMatch, TokenScanner};
+pub use vocabulary::DigitVocabulary;
+
+/// How the first and last digit of a line are found.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Search {
+    /// Tokenise the whole line with `combined_parser`.
+    Tokenise,
+    /// Run every match of the line through a `TokenScanner`.
+    #[default]
+    Scan,
+    /// Search forwards for the first and backwards for the last digit.
+    Bidirectional,
+}
+
+impl FromStr for Search {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tokenise" | "tokenize" => Ok(Search::Tokenise),
+            "scan" => Ok(Search::Scan),
+            "bidirectional" => Ok(Search::Bidirectional),
+            _ => Err(format!("unknown search '{}'", s)),
+        }
+    }
+}
+
+/// What counts as a number in part 2.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Grammar {
+    /// Single digits, written out in the vocabulary or as `0`-`9`.
+    #[default]
+    Digits,
+    /// Whole English numbers from 0 to 999 (`twenty-one`, `one hundred and
+    /// five`) and runs of up to three digits, combined as given. Longer runs
+    /// count digit by digit.
+    Numbers(Combine),
+}
+
+/// `digits`, or `numbers` combined as `Combine::default()`.
+impl FromStr for Grammar {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "digits" => Ok(Grammar::Digits),
+            "numbers" => Ok(Grammar::Numbers(Combine::default())),
+            _ => Err(format!("unknown grammar '{}'", s)),
+        }
+    }
+}
+
+/// What to do with a line that holds no digit at all.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum MissingDigits {
+    /// Fail with a `NoDigitsError` naming the line.
+    #[default]
+    Error,
+    /// Leave the line out of the sum and list it in `CalibrationSum::skipped`.
+    Skip,
+    /// Count the line as 0, silently.
+    Zero,
+}
+
+impl FromStr for MissingDigits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(MissingDigits::Error),
+            "skip" => Ok(MissingDigits::Skip),
+            "zero" => Ok(MissingDigits::Zero),
+            _ => Err(format!("unknown policy '{}'", s)),
+        }
+    }
+}
+
+/// A line without any digit, under `MissingDigits::Error`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NoDigitsError {
+    /// 1-based.
+    pub line: usize,
+    pub content: String,
+}
+
+impl fmt::Display for NoDigitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} has no digits: '{}'", self.line, self.content)
+    }
+}
+
+impl Error for NoDigitsError {}
+
+/// The answer to either part: the sum of the calibration values and the
+/// 1-based lines `MissingDigits::Skip` left out of it.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CalibrationSum {
+    pub total: u64,
+    pub skipped: Vec<usize>,
+}
+
+impl fmt::Display for CalibrationSum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.total)?;
+        if !self.skipped.is_empty() {
+            let lines: Vec<String> = self.skipped.iter().map(usize::to_string).collect();
+            write!(f, " (skipped lines {})", lines.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Day1 {
+    vocabulary: DigitVocabulary,
+    search: Search,
+    grammar: Grammar,
+    missing_digits: MissingDigits,
+    scanner: TokenScanner,
+    bidirectional: BidirectionalSearch,
+}
+
+impl Day1 {
+    pub fn new(vocabulary: DigitVocabulary) -> Day1 {
+        Day1::with_search(vocabulary, Search::default())
+    }
+
+    pub fn with_search(vocabulary: DigitVocabulary, search: Search) -> Day1 {
+        let scanner = TokenScanner::new(&vocabulary);
+        let bidirectional = BidirectionalSearch::new(&vocabulary);
+        Day1 {
+            vocabulary,
+            search,
+            grammar: Grammar::default(),
+            missing_digits: MissingDigits::default(),
+            scanner,
+            bidirectional,
+        }
+    }
+
+    pub fn with_grammar(mut self, grammar: Grammar) -> Day1 {
+        self.grammar = grammar;
+        self
+    }
+
+    pub fn on_missing_digits(mut self, policy: MissingDigits) -> Day1 {
+        self.missing_digits = policy;
+        self
+    }
+
+    /// The names `from_options` reads.
+    pub const OPTIONS: &'static [&'static str] =
+        &["vocabulary", "search", "grammar", "combine", "no-digits"];
+
+    /// Reads `--vocabulary` (a preset name or `word=value,...` pairs),
+    /// `--search` (`tokenise`, `scan` or `bidirectional`), `--grammar`
+    /// (`digits` or `numbers`), `--combine` (`digits` or `numbers`, with
+    /// `--grammar numbers` only) and `--no-digits` (`error`, `skip` or `zero`).
+    /// The numbers grammar is English and always tokenised, so it refuses
+    /// `--vocabulary` and `--search`.
+    pub fn from_options(options: &Options) -> SolutionResult<Day1> {
+        let grammar = match (options.parse("grammar")?, options.parse("combine")?) {
+            (Some(Grammar::Numbers(_)), Some(combine)) => Grammar::Numbers(combine),
+            (_, Some(_)) => return Err("--combine needs --grammar numbers".into()),
+            (grammar, None) => grammar.unwrap_or_default(),
+        };
+        if let Grammar::Numbers(_) = grammar {
+            for name in ["vocabulary", "search"] {
+                if options.get(name).is_some() {
+                    return Err(format!("--grammar numbers cannot be used with --{}", name).into());
+                }
+            }
+        }
+
+        let day = Day1::with_search(
+            options.parse("vocabulary")?.unwrap_or_default(),
+            options.parse("search")?.unwrap_or_default(),
+        );
+        Ok(day
+            .with_grammar(grammar)
+            .on_missing_digits(options.parse("no-digits")?.unwrap_or_default()))
+    }
+
+    pub fn vocabulary(&self) -> &DigitVocabulary {
+        &self.vocabulary
+    }
+
+    pub fn search(&self) -> Search {
+        self.search
+    }
+
+    /// The calibration value of `line`, spelled-out digits included. The
+    /// numbers grammar is always tokenised, whatever the search.
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        if let Grammar::Numbers(combine) = self.grammar {
+            return number_calibration_value(line, combine);
+        }
+        match self.search {
+            Search::Tokenise => tokenised_calibration_value(line, &self.vocabulary),
+            Search::Scan => self.scanner.calibration_value(line),
+            Search::Bidirectional => self.bidirectional.calibration_value(line),
+        }
+    }
+}
+
+impl Default for Day1 {
+    fn default() -> Self {
+        Day1::new(DigitVocabulary::default())
+    }
+}
+
+impl Solution for Day1 {
+    type Input = Vec<String>;
+    type Output = CalibrationSum;
+
+    fn parse(&self, input: &str) -> SolutionResult<Vec<String>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, input: &Vec<String>) -> SolutionResult<CalibrationSum> {
+        match self.search {
+            Search::Bidirectional => {
+                let digits = BidirectionalSearch::new(&DigitVocabulary::new::<String>([]));
+                let value = |line: &str| digits.calibration_value(line);
+                sum_calibration_values(input, self.missing_digits, value)
+            }
+            _ => first_case(input, self.missing_digits),
+        }
+    }
+
+    fn part2(&self, input: &Vec<String>) -> SolutionResult<CalibrationSum> {
+        let value = |line: &str| self.calibration_value(line);
+        sum_calibration_values(input, self.missing_digits, value)
+    }
+}
+
+fn first_case(input: &[String], policy: MissingDigits) -> SolutionResult<CalibrationSum> {
+    sum_calibration_values(input, policy, |line| {
+        let first = line.chars().find_map(|c| c.to_digit(10))?;
+        let last = line.chars().rev().find_map(|c| c.to_digit(10))?;
+        Some(first * 10 + last)
+    })
+}
+
+/// Sums the value of every line, applying `policy` to lines without one.
+fn sum_calibration_values(
+    input: &[String],
+    policy: MissingDigits,
+    calibration_value: impl Fn(&str) -> Option<u32>,
+) -> SolutionResult<CalibrationSum> {
+    let mut sum = CalibrationSum::default();
+    for (index, line) in input.iter().enumerate() {
+        match (calibration_value(line), policy) {
+            (Some(value), _) => {
+                sum.total = sum
+                    .total
+                    .checked_add(u64::from(value))
+                    .ok_or("the sum of calibration values overflows u64")?
+            }
+            (None, MissingDigits::Skip) => sum.skipped.push(index + 1),
+            (None, MissingDigits::Zero) => {}
+            (None, MissingDigits::Error) => {
+                return Err(NoDigitsError {
+                    line: index + 1,
+                    content: line.clone(),
+                }
+                .into())
+            }
+        }
+    }
+    Ok(sum)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expr {
+    Null,
+    Num(u32),
+    /// A whole number from the extended grammar, with the bytes of the line
+    /// it was read from.
+    Number {
+        value: u32,
+        span: Range<usize>,
+    },
+}
+
+impl Expr {
+    pub fn to_number(&self) -> Option<u32> {
+        match self {
+            Expr::Null => None,
+            Expr::Num(x) => Some(*x),
+            Expr::Number { value, .. } => Some(*value),
+        }
+    }
+}
+
+fn digit_parser(input: &str) -> IResult<&str, Expr> {
+    let mut characters = input.chars();
+    let first = characters.next();
+    let rest = characters.as_str();
+    match first {
+        Some(c) => {
+            if c.is_ascii_digit() {
+                Ok((rest, Expr::Num(c.to_digit(10).unwrap())))
+            } else {
+                Err(nom::Err::Error(nom::error::Error {
+                    input,
+                    code: nom::error::ErrorKind::Digit,
+                }))
+            }
+        }
+        None => Err(nom::Err::Error(nom::error::Error {
+            input,
+            code: nom::error::ErrorKind::Digit,
+        })),
+    }
+}
+
+#[allow(dead_code)]
+fn token_parser<'a>(vocabulary: &DigitVocabulary, input: &'a str) -> IResult<&'a str, Expr> {
+    match vocabulary.longest_prefix(input) {
+        Some((word, value)) => Ok((&input[word.len()..], Expr::Num(value))),
+        None => Err(nom::Err::Error(nom::error::Error {
+            input,
+            code: nom::error::ErrorKind::Tag,
+        })),
+    }
+}
+
+/// Like `token_parser`, but only consumes the first character of the word
+/// so that overlapping words such as `eightwo` both get matched.
+fn modified_token_parser<'a>(
+    vocabulary: &DigitVocabulary,
+    input: &'a str,
+) -> IResult<&'a str, Expr> {
+    match vocabulary.longest_prefix(input) {
+        Some((_, value)) => {
+            let mut characters = input.chars();
+            let _ = characters.next();
+            let rest = characters.as_str();
+            Ok((rest, Expr::Num(value)))
+        }
+        None => Err(nom::Err::Error(nom::error::Error {
+            input,
+            code: nom::error::ErrorKind::Tag,
+        })),
+    }
+}
+
+fn character_consumer(input: &str) -> IResult<&str, Expr> {
+    let mut characters = input.chars();
+    let first = characters.next();
+    let rest = characters.as_str();
+    match first {
+        Some(c) => {
+            if c.is_ascii_digit() {
+                Ok((rest, Expr::Num(c.to_digit(10).unwrap())))
+            } else {
+                Ok((rest, Expr::Null))
+            }
+        }
+        None => Ok(("", Expr::Null)),
+    }
+}
+
+fn combined_parser<'a>(vocabulary: &DigitVocabulary, input: &'a str) -> IResult<&'a str, Expr> {
+    alt((
+        |i| modified_token_parser(vocabulary, i),
+        digit_parser,
+        character_consumer,
+    ))(input)
+}
+
+/// The calibration value of `line` read off the `Expr` stream that
+/// `combined_parser` produces. This is the reference the faster
+/// `TokenScanner` is measured and checked against.
+pub fn tokenised_calibration_value(line: &str, vocabulary: &DigitVocabulary) -> Option<u32> {
+    let (_, (parsed_expr, _)) = many_till(|i| combined_parser(vocabulary, i), eof)(line).ok()?;
+    let mut filtered_parsed_expr: Vec<Expr> = parsed_expr
+        .into_iter()
+        .filter(|e: &Expr| match e {
+            Expr::Num(_) | Expr::Number { .. } => true,
+            Expr::Null => false,
+        })
+        .collect();
+
+    match filtered_parsed_expr.len() {
+        0 => None,
+        1 => {
+            let first = filtered_parsed_expr.pop()?.to_number()?;
+            Some(first * 10 + first)
+        }
+        _ => {
+            let first = filtered_parsed_expr[0].to_number()?;
+            let last = filtered_parsed_expr.pop()?.to_number()?;
+            Some(first * 10 + last)
+        }
+    }
+}
+
+#[cfg(test)]
+mod day1_tests {
+    use super::*;
+    use crate::testing::XorShift;
+
+    #[test]
+    fn satisfies_first_case() {
+        let test_input: Vec<String> = ["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let result = first_case(&test_input, MissingDigits::Error).unwrap();
+        assert_eq!(result.total, 142);
+    }
+
+    #[test]
+    fn satisfied_second_case() {
+        let test_input: Vec<String> = [
+            "two65eightbkgqcsn91qxkfvg",
+            "neightwompstbkqv1fourfthdcfgtrkqzgrbfrczxbdn",
+            "43qsrrlxxq",
+            "898dbpjmdqjgtrvdvlxxdnvlfhncdzrt",
+            "jninedsrvftdlcg4hhztwofourskrjhcjvthree",
+            "five562 ",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        let scanner = TokenScanner::new(&DigitVocabulary::english());
+        let result = sum_calibration_values(&test_input, MissingDigits::Error, |l| {
+            scanner.calibration_value(l)
+        })
+        .unwrap();
+        assert_eq!(result.total, 381);
+
+        let search = BidirectionalSearch::new(&DigitVocabulary::english());
+        let result = sum_calibration_values(&test_input, MissingDigits::Error, |l| {
+            search.calibration_value(l)
+        })
+        .unwrap();
+        assert_eq!(result.total, 381);
+
+        let result: u32 = test_input
+            .iter()
+            .filter_map(|line| tokenised_calibration_value(line, &DigitVocabulary::english()))
+            .sum();
+        assert_eq!(result, 381);
+    }
+
+    #[test]
+    fn second_case_with_localised_vocabularies() {
+        let test_input: Vec<String> = ["xzweiab3", "fünfachtneunx", "7einsz"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let scanner = TokenScanner::new(&DigitVocabulary::german());
+        let result = sum_calibration_values(&test_input, MissingDigits::Error, |l| {
+            scanner.calibration_value(l)
+        })
+        .unwrap();
+        assert_eq!(result.total, 23 + 59 + 71);
+
+        let test_input: Vec<String> = ["deuxtroisquatre", "9huitneuf", "a1un"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let scanner = TokenScanner::new(&DigitVocabulary::french());
+        let result = sum_calibration_values(&test_input, MissingDigits::Error, |l| {
+            scanner.calibration_value(l)
+        })
+        .unwrap();
+        assert_eq!(result.total, 24 + 99 + 11);
+    }
+
+    #[test]
+    fn lines_without_digits_follow_the_policy() {
+        let input: Vec<String> = ["1abc2", "nothing", "two"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let error = first_case(&input, MissingDigits::Error).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<NoDigitsError>(),
+            Some(&NoDigitsError {
+                line: 2,
+                content: String::from("nothing")
+            })
+        );
+        assert_eq!(error.to_string(), "line 2 has no digits: 'nothing'");
+        let skipped = first_case(&input, MissingDigits::Skip).unwrap();
+        assert_eq!(skipped.total, 12);
+        assert_eq!(skipped.skipped, vec![2, 3]);
+        assert_eq!(skipped.to_string(), "12 (skipped lines 2, 3)");
+        let zeroed = first_case(&input, MissingDigits::Zero).unwrap();
+        assert_eq!(zeroed.total, 12);
+        assert_eq!(zeroed.skipped, Vec::<usize>::new());
+        assert_eq!(zeroed.to_string(), "12");
+
+        for search in ["tokenise", "scan", "bidirectional"] {
+            let day = Day1::with_search(DigitVocabulary::english(), search.parse().unwrap());
+            let error = day.part2(&input).unwrap_err();
+            assert_eq!(error.to_string(), "line 2 has no digits: 'nothing'");
+
+            let day = day.on_missing_digits(MissingDigits::Skip);
+            assert_eq!(
+                day.part1(&input).unwrap().to_string(),
+                "12 (skipped lines 2, 3)"
+            );
+            assert_eq!(
+                day.part2(&input).unwrap().to_string(),
+                "34 (skipped lines 2)"
+            );
+        }
+
+        let mut options = Options::default();
+        options.set("no-digits", "zero");
+        let day = Day1::from_options(&options).unwrap();
+        assert_eq!(day.part2(&input).unwrap().to_string(), "34");
+        assert!("ignore".parse::<MissingDigits>().is_err());
+    }
+
+    #[test]
+    fn numbers_grammar_from_options() {
+        let input: Vec<String> = ["twelve and 7", "xone hundred and three"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let mut options = Options::default();
+        options.set("grammar", "numbers");
+        let day = Day1::from_options(&options).unwrap();
+        assert_eq!(day.part2(&input).unwrap().total, 17 + 13);
+
+        options.set("combine", "numbers");
+        let day = Day1::from_options(&options).unwrap();
+        assert_eq!(day.part2(&input).unwrap().total, 127 + 103103);
+
+        let many: Vec<String> = vec![String::from("999"); 5000];
+        assert_eq!(day.part2(&many).unwrap().total, 5000 * 999999);
+
+        options.set("vocabulary", "german");
+        assert_eq!(
+            Day1::from_options(&options).unwrap_err().to_string(),
+            "--grammar numbers cannot be used with --vocabulary"
+        );
+        let mut searching = Options::default();
+        searching.set("grammar", "numbers");
+        searching.set("search", "scan");
+        assert!(Day1::from_options(&searching).is_err());
+
+        options.set("grammar", "digits");
+        assert!(Day1::from_options(&options).is_err());
+        options.set("grammar", "roman");
+        assert_eq!(
+            Day1::from_options(&options).unwrap_err().to_string(),
+            "invalid --grammar 'roman': unknown grammar 'roman'"
+        );
+
+        assert_eq!(
+            "numbers".parse::<Grammar>(),
+            Ok(Grammar::Numbers(Combine::Digits))
+        );
+        assert_eq!("digits".parse::<Grammar>(), Ok(Grammar::Digits));
+    }
+
+    /// Lines of digits, vocabulary words (whole and cut short) and filler,
+    /// from a fixed-seed xorshift.
+    fn random_lines(vocabulary: &DigitVocabulary, count: usize) -> Vec<String> {
+        let mut rng = XorShift::new(0x5eed_0001);
+        let mut below = |n: usize| rng.below(n);
+        let words = vocabulary.words();
+        let filler = ['a', 'e', 'n', 'x', 'ü', ' '];
+
+        (0..count)
+            .map(|_| {
+                let mut line = String::new();
+                for _ in 0..below(12) {
+                    match below(6) {
+                        0 => line.push(char::from(b'0' + below(10) as u8)),
+                        1 | 2 => line.push_str(&words[below(words.len())].0),
+                        3 => line.extend(words[below(words.len())].0.chars().skip(1)),
+                        _ => line.push(filler[below(filler.len())]),
+                    }
+                }
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bidirectional_agrees_with_tokenising() {
+        let vocabularies = [
+            DigitVocabulary::english(),
+            DigitVocabulary::german(),
+            DigitVocabulary::french(),
+            DigitVocabulary::new([("ein", 1), ("eins", 7), ("neun", 9), ("un", 3)]),
+        ];
+        for vocabulary in &vocabularies {
+            let search = BidirectionalSearch::new(vocabulary);
+            for line in random_lines(vocabulary, 2000) {
+                assert_eq!(
+                    search.calibration_value(&line),
+                    tokenised_calibration_value(&line, vocabulary),
+                    "line {:?}",
+                    line
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_search_solves_both_parts() {
+        let input: Vec<String> = ["1abc2", "pqr3stu8vwx", "xtwone3four", "treb7uchet"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        for search in ["tokenise", "scan", "bidirectional"] {
+            let day = Day1::with_search(DigitVocabulary::english(), search.parse().unwrap());
+            assert_eq!(day.part1(&input).unwrap().total, 12 + 38 + 33 + 77);
+            assert_eq!(day.part2(&input).unwrap().total, 12 + 38 + 24 + 77);
+        }
+        assert!("backwards".parse::<Search>().is_err());
+    }
+
+    #[test]
+    fn digit_parser_test() {
+        let test_input: &str = "1abc2";
+        let result = digit_parser(test_input);
+
+        assert_eq!(result, Ok(("abc2", Expr::Num(1))));
+    }
+
+    #[test]
+    fn digit_parser_test_fail() {
+        let test_input: &str = "abc2";
+        let result = digit_parser(test_input);
+
+        let error = Err(nom::Err::Error(nom::error::Error {
+            input: "abc2",
+            code: nom::error::ErrorKind::Digit,
+        }));
+
+        assert_eq!(result, error)
+    }
+
+    #[test]
+    fn token_parser_test() {
+        let test_input: &str = "one1abc2";
+        let result = token_parser(&DigitVocabulary::english(), test_input);
+
+        assert_eq!(result, Ok(("1abc2", Expr::Num(1))));
+    }
+
+    #[test]
+    fn token_parser_test_fail() {
+        let test_input: &str = "abc2";
+        let result = token_parser(&DigitVocabulary::english(), test_input);
+
+        let error = Err(nom::Err::Error(nom::error::Error {
+            input: "abc2",
+            code: nom::error::ErrorKind::Tag,
+        }));
+
+        assert_eq!(result, error)
+    }
+
+    #[test]
+    fn combined_parser_test() {
+        let test_input: &str = "one1abc2";
+        let result = combined_parser(&DigitVocabulary::english(), test_input);
+        assert_eq!(result, Ok(("ne1abc2", Expr::Num(1))));
+
+        let test_input: &str = "1abc2";
+        let result = combined_parser(&DigitVocabulary::english(), test_input);
+        assert_eq!(result, Ok(("abc2", Expr::Num(1))));
+
+        let test_input: &str = "abc2";
+        let result = combined_parser(&DigitVocabulary::english(), test_input);
+        assert_eq!(result, Ok(("bc2", Expr::Null)));
+    }
+
+    #[test]
+    fn repeat_parser_test() {
+        let test_input: &str = "onetwoa";
+        let result =
+            many_till(|i| combined_parser(&DigitVocabulary::english(), i), eof)(test_input);
+        assert_eq!(
+            result,
+            Ok((
+                "",
+                (
+                    vec![
+                        Expr::Num(1),
+                        Expr::Null,
+                        Expr::Null,
+                        Expr::Num(2),
+                        Expr::Null,
+                        Expr::Null,
+                        Expr::Null
+                    ],
+                    ""
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn repeat_parser_test_1() {
+        let test_input: &str = "one1abc2";
+        let result =
+            many_till(|i| combined_parser(&DigitVocabulary::english(), i), eof)(test_input);
+        assert_eq!(
+            result,
+            Ok((
+                "",
+                (
+                    vec![
+                        Expr::Num(1),
+                        Expr::Null,
+                        Expr::Null,
+                        Expr::Num(1),
+                        Expr::Null,
+                        Expr::Null,
+                        Expr::Null,
+                        Expr::Num(2)
+                    ],
+                    ""
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn repeat_parser_test_2() {
+        let test_input: &str = "1a2";
+        let result =
+            many_till(|i| combined_parser(&DigitVocabulary::english(), i), eof)(test_input);
+        assert_eq!(
+            result,
+            Ok(("", (vec![Expr::Num(1), Expr::Null, Expr::Num(2)], "")))
+        );
+    }
+
+    #[test]
+    fn repeat_parser_test_3() {
+        let test_input: &str = "a2";
+        let result =
+            many_till(|i| combined_parser(&DigitVocabulary::english(), i), eof)(test_input);
+        assert_eq!(result, Ok(("", (vec![Expr::Null, Expr::Num(2)], ""))));
+    }
+}
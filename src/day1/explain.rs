@@ -0,0 +1,143 @@
+use std::fmt;
+
+use super::{combined_parser, DigitVocabulary, Expr};
+
+/// A digit in the `Expr` stream of a line, with the text it was read from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExplainedToken {
+    pub offset: usize,
+    pub text: String,
+    pub value: u32,
+}
+
+/// Every digit `combined_parser` found in a line, and which of them make up
+/// the calibration value.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Explanation {
+    pub line: String,
+    pub tokens: Vec<ExplainedToken>,
+}
+
+impl Explanation {
+    pub fn first(&self) -> Option<&ExplainedToken> {
+        self.tokens.first()
+    }
+
+    pub fn last(&self) -> Option<&ExplainedToken> {
+        self.tokens.last()
+    }
+
+    pub fn calibration_value(&self) -> Option<u32> {
+        Some(self.first()?.value * 10 + self.last()?.value)
+    }
+}
+
+pub fn explain(line: &str, vocabulary: &DigitVocabulary) -> Explanation {
+    let mut tokens = vec![];
+    let mut rest = line;
+
+    while let Ok((next, expr)) = combined_parser(vocabulary, rest) {
+        if let Expr::Num(value) = expr {
+            let text = match vocabulary.longest_prefix(rest) {
+                Some((word, _)) => word,
+                None => &rest[..rest.len() - next.len()],
+            };
+            tokens.push(ExplainedToken {
+                offset: line.len() - rest.len(),
+                text: text.to_string(),
+                value,
+            });
+        }
+        if next.is_empty() {
+            break;
+        }
+        rest = next;
+    }
+
+    Explanation {
+        line: line.to_string(),
+        tokens,
+    }
+}
+
+/// The line, one row per token (`@offset text value`, then `first`/`last`)
+/// and the resulting value.
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.line)?;
+        let width = self
+            .tokens
+            .iter()
+            .map(|t| t.text.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let count = self.tokens.len();
+        for (i, token) in self.tokens.iter().enumerate() {
+            let chosen = match (i == 0, i + 1 == count) {
+                (true, true) => "  first, last",
+                (true, false) => "  first",
+                (false, true) => "  last",
+                (false, false) => "",
+            };
+            writeln!(
+                f,
+                "  @{:<4} {:<width$} {}{}",
+                token.offset,
+                token.text,
+                token.value,
+                chosen,
+                width = width
+            )?;
+        }
+
+        match self.calibration_value() {
+            Some(value) => writeln!(f, "  => {}", value),
+            None => writeln!(f, "  => no digits"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod explain_tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_overlapping_words() {
+        let explanation = explain("xtwone3four", &DigitVocabulary::english());
+        let tokens: Vec<(usize, &str, u32)> = explanation
+            .tokens
+            .iter()
+            .map(|t| (t.offset, t.text.as_str(), t.value))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![(1, "two", 2), (3, "one", 1), (6, "3", 3), (7, "four", 4)]
+        );
+        assert_eq!(explanation.calibration_value(), Some(24));
+        assert_eq!(
+            explanation.to_string(),
+            "xtwone3four
+  @1    two  2  first
+  @3    one  1
+  @6    3    3
+  @7    four 4  last
+  => 24
+"
+        );
+    }
+
+    #[test]
+    fn test_explain_single_and_missing_digits() {
+        let explanation = explain("füfünf", &DigitVocabulary::german());
+        assert_eq!(explanation.tokens[0].offset, 3);
+        assert_eq!(
+            explanation.to_string(),
+            "füfünf\n  @3    fünf 5  first, last\n  => 55\n"
+        );
+
+        let explanation = explain("abc", &DigitVocabulary::english());
+        assert!(explanation.tokens.is_empty());
+        assert_eq!(explanation.to_string(), "abc\n  => no digits\n");
+    }
+}
@@ -0,0 +1,334 @@
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{anychar, digit1, one_of};
+use nom::combinator::{consumed, eof, map, opt, value, verify};
+use nom::multi::many_till;
+use nom::sequence::{pair, preceded, tuple};
+use nom::IResult;
+use nom_locate::LocatedSpan;
+
+use super::Expr;
+
+type Span<'a> = LocatedSpan<&'a str>;
+
+const UNITS: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const TEENS: [(&str, u32); 10] = [
+    ("ten", 10),
+    ("eleven", 11),
+    ("twelve", 12),
+    ("thirteen", 13),
+    ("fourteen", 14),
+    ("fifteen", 15),
+    ("sixteen", 16),
+    ("seventeen", 17),
+    ("eighteen", 18),
+    ("nineteen", 19),
+];
+
+const TENS: [(&str, u32); 8] = [
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+];
+
+/// How the calibration value is built from the numbers of a line.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Combine {
+    /// The leading digit of the first number and the last digit of the last
+    /// (`twenty-one ... twelve` gives 22).
+    #[default]
+    Digits,
+    /// The first and last numbers written one after the other
+    /// (`twenty-one ... twelve` gives 2112).
+    Numbers,
+}
+
+impl FromStr for Combine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "digits" => Ok(Combine::Digits),
+            "numbers" => Ok(Combine::Numbers),
+            _ => Err(format!("unknown combine mode '{}'", s)),
+        }
+    }
+}
+
+fn word_parser<'a>(words: &[(&str, u32)], input: Span<'a>) -> IResult<Span<'a>, u32> {
+    for &(word, value) in words {
+        if let Ok((rest, _)) = tag::<_, _, nom::error::Error<Span<'a>>>(word)(input) {
+            return Ok((rest, value));
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error {
+        input,
+        code: nom::error::ErrorKind::Tag,
+    }))
+}
+
+fn nonzero_unit_parser(input: Span) -> IResult<Span, u32> {
+    word_parser(&UNITS[1..], input)
+}
+
+fn separator_parser(input: Span) -> IResult<Span, char> {
+    one_of(" -")(input)
+}
+
+/// `forty`, `forty-two`, `twelve` or `two`.
+fn below_hundred_parser(input: Span) -> IResult<Span, u32> {
+    alt((
+        map(
+            pair(
+                |i| word_parser(&TENS, i),
+                opt(preceded(tag("-"), nonzero_unit_parser)),
+            ),
+            |(tens, unit)| tens + unit.unwrap_or(0),
+        ),
+        |i| word_parser(&TEENS, i),
+        |i| word_parser(&UNITS, i),
+    ))(input)
+}
+
+/// `three hundred`, `three-hundred and forty-two`, `threehundredtwelve`.
+fn hundreds_parser(input: Span) -> IResult<Span, u32> {
+    let rest_parser = preceded(
+        pair(
+            opt(separator_parser),
+            opt(pair(tag("and"), opt(separator_parser))),
+        ),
+        verify(below_hundred_parser, |&v| v > 0),
+    );
+    map(
+        tuple((
+            nonzero_unit_parser,
+            preceded(opt(separator_parser), tag("hundred")),
+            opt(rest_parser),
+        )),
+        |(hundreds, _, rest)| hundreds * 100 + rest.unwrap_or(0),
+    )(input)
+}
+
+fn number_expr((matched, value): (Span, u32)) -> Expr {
+    let start = matched.location_offset();
+    Expr::Number {
+        value,
+        span: start..start + matched.fragment().len(),
+    }
+}
+
+/// The longest spelled-out number from 0 to 999 starting at `input`.
+fn word_number_parser(input: Span) -> IResult<Span, Expr> {
+    map(
+        consumed(alt((hundreds_parser, below_hundred_parser))),
+        number_expr,
+    )(input)
+}
+
+/// A whole run of digits: one number if it is at most 999, otherwise each
+/// digit on its own, as the digits grammar would read it.
+fn digit_run_parser(input: Span) -> IResult<Span, Vec<Expr>> {
+    let (rest, run) = digit1(input)?;
+    if run.len() <= 3 {
+        let value = run.fragment().parse::<u32>().unwrap_or_default();
+        return Ok((rest, vec![number_expr((run, value))]));
+    }
+
+    let start = run.location_offset();
+    let digits = run.fragment().bytes().enumerate();
+    let exprs = digits
+        .map(|(i, digit)| Expr::Number {
+            value: u32::from(digit - b'0'),
+            span: start + i..start + i + 1,
+        })
+        .collect();
+    Ok((rest, exprs))
+}
+
+/// Like `modified_token_parser` for numbers: reads the number starting here
+/// but only consumes one character, so overlapping words are all found.
+/// Digit runs cannot overlap anything and are consumed whole.
+fn combined_number_parser(input: Span) -> IResult<Span, Vec<Expr>> {
+    alt((
+        digit_run_parser,
+        |input| {
+            let (_, number) = word_number_parser(input)?;
+            let (rest, _) = anychar(input)?;
+            Ok((rest, vec![number]))
+        },
+        value(vec![], anychar),
+    ))(input)
+}
+
+/// The numbers of `line` as `Expr::Number`s, in order. A number lying wholly
+/// inside an earlier one (`one` in `twenty-one`) is not counted again, while
+/// overlapping ones (`eight` and `two` in `eightwo`) both are.
+pub fn numbers(line: &str) -> Vec<Expr> {
+    let Ok((_, (parsed, _))) = many_till(combined_number_parser, eof)(Span::new(line)) else {
+        return vec![];
+    };
+
+    let mut covered = 0;
+    parsed
+        .into_iter()
+        .flatten()
+        .filter(|expr| match expr {
+            Expr::Number { span, .. } if span.end > covered => {
+                covered = span.end;
+                true
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+/// The calibration value of `line`, or `None` if it holds no number. Every
+/// number is at most 999, so the value is at most 999999.
+pub fn number_calibration_value(line: &str, combine: Combine) -> Option<u32> {
+    let numbers = numbers(line);
+    let first = numbers.first()?.to_number()?;
+    let last = numbers.last()?.to_number()?;
+    let width = |n: u32| n.checked_ilog10().unwrap_or(0) + 1;
+
+    match combine {
+        Combine::Digits => Some(first / 10u32.pow(width(first) - 1) * 10 + last % 10),
+        Combine::Numbers => Some(first * 10u32.pow(width(last)) + last),
+    }
+}
+
+#[cfg(test)]
+mod numbers_tests {
+    use super::*;
+
+    fn values(line: &str) -> Vec<u32> {
+        numbers(line).iter().filter_map(Expr::to_number).collect()
+    }
+
+    #[test]
+    fn test_word_number_parser() {
+        let cases = [
+            ("zero", 0),
+            ("seven", 7),
+            ("seventeen", 17),
+            ("seventy", 70),
+            ("seventy-seven", 77),
+            ("twelve", 12),
+            ("one hundred", 100),
+            ("nine-hundred and ninety-nine", 999),
+            ("twohundredfive", 205),
+        ];
+        for (input, expected) in cases {
+            let (rest, expr) = word_number_parser(Span::new(input)).unwrap();
+            assert_eq!(
+                expr,
+                Expr::Number {
+                    value: expected,
+                    span: 0..input.len()
+                },
+                "{}",
+                input
+            );
+            assert!(rest.fragment().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_word_number_parser_stops_at_the_longest_number() {
+        let (rest, expr) = word_number_parser(Span::new("forty-zero")).unwrap();
+        assert_eq!(expr.to_number(), Some(40));
+        assert_eq!(*rest.fragment(), "-zero");
+
+        let (rest, expr) = word_number_parser(Span::new("one hundred and")).unwrap();
+        assert_eq!(expr.to_number(), Some(100));
+        assert_eq!(*rest.fragment(), " and");
+
+        assert!(word_number_parser(Span::new("hundred")).is_err());
+    }
+
+    #[test]
+    fn test_digit_run_parser() {
+        let (rest, exprs) = digit_run_parser(Span::new("408x")).unwrap();
+        assert_eq!(
+            exprs,
+            vec![Expr::Number {
+                value: 408,
+                span: 0..3
+            }]
+        );
+        assert_eq!(*rest.fragment(), "x");
+
+        let (rest, exprs) = digit_run_parser(Span::new("1234")).unwrap();
+        let digits: Vec<(Option<u32>, usize)> = exprs
+            .iter()
+            .map(|e| match e {
+                Expr::Number { value, span } => (Some(*value), span.start),
+                _ => (None, 0),
+            })
+            .collect();
+        assert_eq!(
+            digits,
+            vec![(Some(1), 0), (Some(2), 1), (Some(3), 2), (Some(4), 3)]
+        );
+        assert!(rest.fragment().is_empty());
+    }
+
+    #[test]
+    fn test_numbers_in_a_line() {
+        assert_eq!(values("xtwenty-one3fourteen"), vec![21, 3, 14]);
+        assert_eq!(values("eightwo"), vec![8, 2]);
+        assert_eq!(values("a123b"), vec![123]);
+        assert_eq!(values("a12345b"), vec![1, 2, 3, 4, 5]);
+        assert_eq!(values("one1234two"), vec![1, 1, 2, 3, 4, 2]);
+        assert_eq!(values("nothing"), vec![]);
+
+        let spans: Vec<Expr> = numbers("ab twelve");
+        assert_eq!(
+            spans,
+            vec![Expr::Number {
+                value: 12,
+                span: 3..9
+            }]
+        );
+    }
+
+    #[test]
+    fn test_number_calibration_value() {
+        let line = "xtwenty-one3fourteen";
+        assert_eq!(number_calibration_value(line, Combine::Digits), Some(24));
+        assert_eq!(number_calibration_value(line, Combine::Numbers), Some(2114));
+        assert_eq!(number_calibration_value("zero", Combine::Numbers), Some(0));
+        assert_eq!(number_calibration_value("zero", Combine::Digits), Some(0));
+        assert_eq!(
+            number_calibration_value("x50y7", Combine::Numbers),
+            Some(507)
+        );
+        assert_eq!(
+            number_calibration_value("x1234y", Combine::Numbers),
+            Some(14)
+        );
+        assert_eq!(
+            number_calibration_value("nine hundred and ninety-nine", Combine::Numbers),
+            Some(999999)
+        );
+        assert_eq!(number_calibration_value("abc", Combine::Digits), None);
+    }
+}
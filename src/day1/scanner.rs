@@ -0,0 +1,323 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use super::DigitVocabulary;
+
+const ROOT: usize = 0;
+const NONE: u32 = u32::MAX;
+
+/// A digit found in a line: `len` bytes starting at byte `offset`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TokenMatch {
+    pub offset: usize,
+    pub len: usize,
+    pub value: u32,
+}
+
+/// Aho-Corasick automaton over the vocabulary words plus the ASCII digits.
+/// Scanning is a single pass over the bytes of a line and reports every
+/// match, overlapping ones included (`eightwo` gives 8 and 2).
+#[derive(Debug, Clone)]
+pub struct TokenScanner {
+    transitions: Vec<[u32; 256]>,
+    /// `(length, value)` of every pattern ending in each state, longest first.
+    outputs: Vec<Vec<(usize, u32)>>,
+}
+
+impl TokenScanner {
+    pub fn new(vocabulary: &DigitVocabulary) -> TokenScanner {
+        TokenScanner::build(patterns(vocabulary))
+    }
+
+    /// A scanner for the words spelled backwards, to be run over the bytes
+    /// of a line from the end.
+    fn reversed(vocabulary: &DigitVocabulary) -> TokenScanner {
+        TokenScanner::build(patterns(vocabulary).map(|(mut word, value)| {
+            word.reverse();
+            (word, value)
+        }))
+    }
+
+    fn build(patterns: impl Iterator<Item = (Vec<u8>, u32)>) -> TokenScanner {
+        let mut transitions = vec![[NONE; 256]];
+        let mut outputs: Vec<Vec<(usize, u32)>> = vec![vec![]];
+
+        for (word, value) in patterns {
+            let mut state = ROOT;
+            for &byte in &word {
+                let next = transitions[state][byte as usize];
+                state = if next == NONE {
+                    transitions.push([NONE; 256]);
+                    outputs.push(vec![]);
+                    let created = transitions.len() - 1;
+                    transitions[state][byte as usize] = created as u32;
+                    created
+                } else {
+                    next as usize
+                };
+            }
+            if !word.is_empty() {
+                outputs[state].push((word.len(), value));
+            }
+        }
+
+        // Breadth-first over the trie, filling the missing transitions from
+        // each state's failure link so the result is a complete DFA.
+        let mut fail = vec![ROOT; transitions.len()];
+        let mut queue = VecDeque::new();
+        for next in transitions[ROOT].iter_mut() {
+            match *next {
+                NONE => *next = ROOT as u32,
+                child => queue.push_back(child as usize),
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+            outputs[state].sort_by_key(|&(len, _)| Reverse(len));
+
+            let fallbacks = transitions[fail[state]];
+            for (next, fallback) in transitions[state].iter_mut().zip(fallbacks) {
+                match *next {
+                    NONE => *next = fallback,
+                    child => {
+                        fail[child as usize] = fallback as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+
+        TokenScanner {
+            transitions,
+            outputs,
+        }
+    }
+
+    /// Every match in `line`, ordered by where it ends (longest first when
+    /// several end on the same byte).
+    pub fn matches<'s>(&'s self, line: &'s str) -> Matches<'s> {
+        Matches {
+            scanner: self,
+            bytes: line.as_bytes(),
+            position: 0,
+            state: ROOT,
+            pending: 0,
+        }
+    }
+
+    /// The first and last digit of `line` as a two-digit number, or `None`
+    /// when the line holds no digit at all.
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        let mut first: Option<TokenMatch> = None;
+        let mut last: Option<TokenMatch> = None;
+
+        for m in self.matches(line) {
+            if first.is_none_or(|f| (m.offset, Reverse(m.len)) < (f.offset, Reverse(f.len))) {
+                first = Some(m);
+            }
+            if last.is_none_or(|l| (m.offset, m.len) > (l.offset, l.len)) {
+                last = Some(m);
+            }
+        }
+
+        Some(first?.value * 10 + last?.value)
+    }
+}
+
+/// The vocabulary words and the ASCII digits, as bytes.
+fn patterns(vocabulary: &DigitVocabulary) -> impl Iterator<Item = (Vec<u8>, u32)> + '_ {
+    let words = vocabulary
+        .words()
+        .iter()
+        .map(|(word, value)| (word.as_bytes().to_vec(), *value));
+    words.chain((0..10).map(|d| (d.to_string().into_bytes(), d)))
+}
+
+/// Finds only the first and last digit of a line: forwards from the start
+/// until no later match can begin earlier, and backwards from the end over
+/// the reversed words, where the first hit is the match starting last. The
+/// bytes between the two are never looked at.
+#[derive(Debug, Clone)]
+pub struct BidirectionalSearch {
+    forward: TokenScanner,
+    backward: TokenScanner,
+    longest: usize,
+}
+
+impl BidirectionalSearch {
+    pub fn new(vocabulary: &DigitVocabulary) -> BidirectionalSearch {
+        BidirectionalSearch {
+            forward: TokenScanner::new(vocabulary),
+            backward: TokenScanner::reversed(vocabulary),
+            longest: patterns(vocabulary)
+                .map(|(word, _)| word.len())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// The match starting first, the longest one if several start there.
+    pub fn first(&self, line: &str) -> Option<TokenMatch> {
+        let mut best: Option<TokenMatch> = None;
+        let mut state = ROOT;
+
+        for (position, &byte) in line.as_bytes().iter().enumerate() {
+            state = self.forward.transitions[state][byte as usize] as usize;
+            let end = position + 1;
+            if let Some(&(len, value)) = self.forward.outputs[state].first() {
+                let offset = end - len;
+                if best.is_none_or(|b| (offset, Reverse(len)) < (b.offset, Reverse(b.len))) {
+                    best = Some(TokenMatch { offset, len, value });
+                }
+            }
+            // Anything ending past here starts after the best match so far.
+            if best.is_some_and(|b| end >= b.offset + self.longest) {
+                break;
+            }
+        }
+
+        best
+    }
+
+    /// The match starting last, the longest one if several start there.
+    pub fn last(&self, line: &str) -> Option<TokenMatch> {
+        let bytes = line.as_bytes();
+        let mut state = ROOT;
+
+        for (consumed, &byte) in bytes.iter().rev().enumerate() {
+            state = self.backward.transitions[state][byte as usize] as usize;
+            if let Some(&(len, value)) = self.backward.outputs[state].first() {
+                return Some(TokenMatch {
+                    offset: bytes.len() - consumed - 1,
+                    len,
+                    value,
+                });
+            }
+        }
+
+        None
+    }
+
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        Some(self.first(line)?.value * 10 + self.last(line)?.value)
+    }
+}
+
+pub struct Matches<'s> {
+    scanner: &'s TokenScanner,
+    bytes: &'s [u8],
+    position: usize,
+    state: usize,
+    pending: usize,
+}
+
+impl Iterator for Matches<'_> {
+    type Item = TokenMatch;
+
+    fn next(&mut self) -> Option<TokenMatch> {
+        loop {
+            let outputs = &self.scanner.outputs[self.state];
+            if let Some(&(len, value)) = outputs.get(self.pending) {
+                self.pending += 1;
+                return Some(TokenMatch {
+                    offset: self.position - len,
+                    len,
+                    value,
+                });
+            }
+
+            let &byte = self.bytes.get(self.position)?;
+            self.state = self.scanner.transitions[self.state][byte as usize] as usize;
+            self.position += 1;
+            self.pending = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod scanner_tests {
+    use super::*;
+
+    fn values(scanner: &TokenScanner, line: &str) -> Vec<(usize, u32)> {
+        scanner.matches(line).map(|m| (m.offset, m.value)).collect()
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let scanner = TokenScanner::new(&DigitVocabulary::english());
+        assert_eq!(values(&scanner, "eightwo"), vec![(0, 8), (4, 2)]);
+        assert_eq!(values(&scanner, "oneight"), vec![(0, 1), (2, 8)]);
+        assert_eq!(
+            values(&scanner, "xtwone3four"),
+            vec![(1, 2), (3, 1), (6, 3), (7, 4)]
+        );
+        assert_eq!(values(&scanner, "abc"), vec![]);
+    }
+
+    #[test]
+    fn test_nested_words_share_an_end() {
+        let vocabulary = DigitVocabulary::new([("eins", 1), ("ins", 7)]);
+        let scanner = TokenScanner::new(&vocabulary);
+        let found: Vec<TokenMatch> = scanner.matches("eins").collect();
+        assert_eq!(
+            found,
+            vec![
+                TokenMatch {
+                    offset: 0,
+                    len: 4,
+                    value: 1
+                },
+                TokenMatch {
+                    offset: 1,
+                    len: 3,
+                    value: 7
+                }
+            ]
+        );
+        assert_eq!(scanner.calibration_value("eins"), Some(17));
+    }
+
+    #[test]
+    fn test_calibration_value() {
+        let scanner = TokenScanner::new(&DigitVocabulary::english());
+        assert_eq!(scanner.calibration_value("two1nine"), Some(29));
+        assert_eq!(scanner.calibration_value("zoneight234"), Some(14));
+        assert_eq!(scanner.calibration_value("7pqrstsixteen"), Some(76));
+        assert_eq!(scanner.calibration_value("treb7uchet"), Some(77));
+        assert_eq!(scanner.calibration_value("nothing"), None);
+    }
+
+    #[test]
+    fn test_multibyte_words() {
+        let scanner = TokenScanner::new(&DigitVocabulary::german());
+        assert_eq!(values(&scanner, "xfünfacht"), vec![(1, 5), (6, 8)]);
+    }
+
+    #[test]
+    fn test_bidirectional_first_and_last() {
+        let search = BidirectionalSearch::new(&DigitVocabulary::english());
+        let first = search.first("xtwone3four").unwrap();
+        let last = search.last("xtwone3four").unwrap();
+        assert_eq!((first.offset, first.value), (1, 2));
+        assert_eq!((last.offset, last.len, last.value), (7, 4, 4));
+
+        assert_eq!(search.calibration_value("eightwo"), Some(82));
+        assert_eq!(search.calibration_value("4"), Some(44));
+        assert_eq!(search.calibration_value("nothing"), None);
+        assert_eq!(search.calibration_value(""), None);
+    }
+
+    #[test]
+    fn test_bidirectional_prefers_the_earliest_start() {
+        // `ins` ends before `xeins` does, but `xeins` starts first.
+        let vocabulary = DigitVocabulary::new([("xeins", 1), ("ins", 7)]);
+        let search = BidirectionalSearch::new(&vocabulary);
+        assert_eq!(search.first("xeins").map(|m| m.value), Some(1));
+        assert_eq!(search.last("xeins").map(|m| m.value), Some(7));
+
+        let search = BidirectionalSearch::new(&DigitVocabulary::german());
+        assert_eq!(search.calibration_value("xfünfacht"), Some(58));
+    }
+}
@@ -0,0 +1,167 @@
+use std::str::FromStr;
+
+/// The spelled-out words that count as digits, each with its value.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DigitVocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl DigitVocabulary {
+    /// Drops empty words, which would otherwise match before every
+    /// character.
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = (S, u32)>) -> DigitVocabulary {
+        DigitVocabulary {
+            words: words
+                .into_iter()
+                .map(|(w, v)| (w.into(), v))
+                .filter(|(w, _)| !w.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn english() -> DigitVocabulary {
+        DigitVocabulary::new([
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ])
+    }
+
+    pub fn german() -> DigitVocabulary {
+        DigitVocabulary::new([
+            ("eins", 1),
+            ("zwei", 2),
+            ("drei", 3),
+            ("vier", 4),
+            ("fünf", 5),
+            ("sechs", 6),
+            ("sieben", 7),
+            ("acht", 8),
+            ("neun", 9),
+        ])
+    }
+
+    pub fn french() -> DigitVocabulary {
+        DigitVocabulary::new([
+            ("un", 1),
+            ("deux", 2),
+            ("trois", 3),
+            ("quatre", 4),
+            ("cinq", 5),
+            ("six", 6),
+            ("sept", 7),
+            ("huit", 8),
+            ("neuf", 9),
+        ])
+    }
+
+    pub fn preset(name: &str) -> Option<DigitVocabulary> {
+        match name {
+            "english" | "en" => Some(DigitVocabulary::english()),
+            "german" | "de" => Some(DigitVocabulary::german()),
+            "french" | "fr" => Some(DigitVocabulary::french()),
+            _ => None,
+        }
+    }
+
+    pub fn words(&self) -> &[(String, u32)] {
+        &self.words
+    }
+
+    /// The longest word `input` starts with, and its value.
+    pub fn longest_prefix(&self, input: &str) -> Option<(&str, u32)> {
+        self.words
+            .iter()
+            .filter(|(w, _)| input.starts_with(w.as_str()))
+            .max_by_key(|(w, _)| w.len())
+            .map(|(w, v)| (w.as_str(), *v))
+    }
+}
+
+impl Default for DigitVocabulary {
+    fn default() -> Self {
+        DigitVocabulary::english()
+    }
+}
+
+/// Accepts a preset name (`english`, `german`, `french`) or a list of
+/// `word=value` pairs such as `uno=1,dos=2,tres=3`.
+impl FromStr for DigitVocabulary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(vocabulary) = DigitVocabulary::preset(s) {
+            return Ok(vocabulary);
+        }
+        if !s.contains('=') {
+            return Err(format!("unknown vocabulary '{}'", s));
+        }
+
+        let words = s
+            .split(',')
+            .map(|pair| {
+                let (word, value) = pair
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected word=value, got '{}'", pair))?;
+                let word = word.trim();
+                if word.is_empty() {
+                    return Err(format!("missing word before '=' in '{}'", pair));
+                }
+                let value = value
+                    .trim()
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&value| value <= 9)
+                    .ok_or_else(|| format!("'{}' is not a digit 0-9 for '{}'", value, word))?;
+                Ok((word.to_string(), value))
+            })
+            .collect::<Result<Vec<(String, u32)>, String>>()?;
+        Ok(DigitVocabulary::new(words))
+    }
+}
+
+#[cfg(test)]
+mod vocabulary_tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_prefix() {
+        let vocabulary = DigitVocabulary::new([("ein", 1), ("eins", 1), ("neun", 9)]);
+        assert_eq!(vocabulary.longest_prefix("einsneun"), Some(("eins", 1)));
+        assert_eq!(vocabulary.longest_prefix("neun"), Some(("neun", 9)));
+        assert_eq!(vocabulary.longest_prefix("zwei"), None);
+
+        let vocabulary = DigitVocabulary::new([("", 5), ("one", 1)]);
+        assert_eq!(vocabulary, DigitVocabulary::new([("one", 1)]));
+        assert_eq!(vocabulary.longest_prefix("x"), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "german".parse::<DigitVocabulary>(),
+            Ok(DigitVocabulary::german())
+        );
+        assert_eq!(
+            "uno=1, dos=2".parse::<DigitVocabulary>(),
+            Ok(DigitVocabulary::new([("uno", 1), ("dos", 2)]))
+        );
+        assert!("klingon".parse::<DigitVocabulary>().is_err());
+        assert!("uno=one".parse::<DigitVocabulary>().is_err());
+        assert_eq!(
+            "=1,dos=2".parse::<DigitVocabulary>(),
+            Err(String::from("missing word before '=' in '=1'"))
+        );
+        assert_eq!(
+            "diez=10".parse::<DigitVocabulary>(),
+            Err(String::from("'10' is not a digit 0-9 for 'diez'"))
+        );
+        assert!("cero=0, nueve=9".parse::<DigitVocabulary>().is_ok());
+    }
+}
@@ -0,0 +1,797 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, digit1, space0};
+use nom::combinator::{eof, map_res};
+use nom::error::{ErrorKind, FromExternalError};
+use nom::multi::separated_list1;
+use nom::sequence::pair;
+use nom::{IResult, Slice};
+use nom_locate::LocatedSpan;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::input::read_input;
+use crate::solution::{Options, Solution, SolutionResult};
+
+mod bag;
+mod colour;
+mod infer;
+mod query;
+
+pub use bag::{Bag, Infeasible, Violation};
+pub use colour::Colour;
+pub use infer::{
+    candidate_bags, game_log_likelihood, infer, most_likely_bag, pick_log_likelihood, Estimate,
+    Inference, TooManyCandidates, DEFAULT_LIMIT, MAX_CANDIDATES,
+};
+pub use query::{select, Op, Query, QueryError, Value};
+
+type Span<'a> = LocatedSpan<&'a str>;
+type GameResult<'a, T> = IResult<Span<'a>, T, ParseError>;
+
+/// What to do with a line that is not a well-formed game.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ParseMode {
+    /// Fail the whole input on the first bad line.
+    #[default]
+    Strict,
+    /// Leave bad lines out, listing each from `parse_with_skipped`.
+    Lenient,
+}
+
+impl FromStr for ParseMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(ParseMode::Strict),
+            "lenient" => Ok(ParseMode::Lenient),
+            _ => Err(format!("unknown parse mode '{}'", s)),
+        }
+    }
+}
+
+/// Where a line stopped matching `Game N: count colour, ...; ...` and what
+/// was expected there.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, in characters.
+    pub column: usize,
+    pub expected: &'static str,
+}
+
+impl ParseError {
+    fn at(input: Span, expected: &'static str) -> ParseError {
+        ParseError {
+            line: input.location_line() as usize,
+            column: input.get_utf8_column(),
+            expected,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} at {}:{}",
+            self.expected, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+impl<'a> nom::error::ParseError<Span<'a>> for ParseError {
+    fn from_error_kind(input: Span<'a>, _: ErrorKind) -> Self {
+        ParseError::at(input, "a game")
+    }
+
+    fn append(_: Span<'a>, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a, E> FromExternalError<Span<'a>, E> for ParseError {
+    fn from_external_error(input: Span<'a>, _: ErrorKind, _: E) -> Self {
+        ParseError::at(input, "a game")
+    }
+}
+
+/// What a pick that names the same colour twice (`3 red, 4 red`) means.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum RepeatedColours {
+    /// It is a parse error, reported at the repeat.
+    #[default]
+    Error,
+    /// The counts add up: 7 red.
+    Sum,
+    /// The larger count is kept: 4 red.
+    Max,
+}
+
+impl FromStr for RepeatedColours {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(RepeatedColours::Error),
+            "sum" => Ok(RepeatedColours::Sum),
+            "max" => Ok(RepeatedColours::Max),
+            _ => Err(format!("unknown policy '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Day2 {
+    pub bag: Bag,
+    pub mode: ParseMode,
+    pub repeats: RepeatedColours,
+}
+
+impl Day2 {
+    /// The names `from_options` reads.
+    pub const OPTIONS: &'static [&'static str] = &["bag", "bag-file", "parse", "repeats"];
+
+    /// Reads the bag for part 1 from `--bag` (`red=12,green=13,blue=14`) or
+    /// from the file named by `--bag-file`, one `colour = count` per line,
+    /// `--parse` (`strict` or `lenient`) and `--repeats` (`error`, `sum` or
+    /// `max`).
+    pub fn from_options(options: &Options) -> SolutionResult<Day2> {
+        let bag = match (options.parse("bag")?, options.get("bag-file")) {
+            (Some(_), Some(_)) => return Err("give either --bag or --bag-file, not both".into()),
+            (Some(bag), None) => bag,
+            (None, Some(path)) => read_input(path)?
+                .parse()
+                .map_err(|e| format!("invalid bag in {}: {}", path, e))?,
+            (None, None) => Bag::default(),
+        };
+        let mode = options.parse("parse")?.unwrap_or_default();
+        let repeats = options.parse("repeats")?.unwrap_or_default();
+        Ok(Day2 { bag, mode, repeats })
+    }
+}
+
+impl Solution for Day2 {
+    type Input = Vec<GameData>;
+    type Output = u64;
+
+    fn parse(&self, input: &str) -> SolutionResult<Vec<GameData>> {
+        Ok(self.parse_with_skipped(input)?.0)
+    }
+
+    fn parse_with_skipped(&self, input: &str) -> SolutionResult<(Vec<GameData>, Vec<String>)> {
+        match self.mode {
+            ParseMode::Strict => Ok((parse_games(input, self.repeats)?, vec![])),
+            ParseMode::Lenient => {
+                let (games, skipped) = parse_games_lenient(input, self.repeats);
+                Ok((games, skipped.iter().map(ParseError::to_string).collect()))
+            }
+        }
+    }
+
+    fn part1(&self, input: &Vec<GameData>) -> SolutionResult<u64> {
+        first_case(input, &self.bag)
+    }
+
+    fn part2(&self, input: &Vec<GameData>) -> SolutionResult<u64> {
+        second_case(input, &self.bag)
+    }
+}
+
+/// The cubes shown in one handful, by colour. Colours that were not shown
+/// count as 0.
+#[derive(Debug, Clone, Default)]
+pub struct Pick {
+    counts: BTreeMap<Colour, u32>,
+    /// The policy the counts were merged under, if a colour was repeated.
+    merged: Option<RepeatedColours>,
+}
+
+impl Pick {
+    pub fn new<'a>(counts: impl IntoIterator<Item = (&'a str, u32)>) -> Pick {
+        Pick {
+            counts: counts
+                .into_iter()
+                .map(|(name, count)| (Colour::new(name), count))
+                .collect(),
+            merged: None,
+        }
+    }
+
+    pub fn count(&self, colour: Colour) -> u32 {
+        self.counts.get(&colour).copied().unwrap_or(0)
+    }
+
+    /// `Some(policy)` when the pick as written named a colour more than once
+    /// and its counts were combined by `policy`.
+    pub fn merged(&self) -> Option<RepeatedColours> {
+        self.merged
+    }
+
+    /// The colours shown, in name order, with their counts.
+    pub fn counts(&self) -> impl Iterator<Item = (Colour, u32)> + '_ {
+        self.counts.iter().map(|(&colour, &count)| (colour, count))
+    }
+}
+
+/// Picks are equal when they show the same cubes; how repeated colours were
+/// merged on the way in is not part of the value.
+impl PartialEq for Pick {
+    fn eq(&self, other: &Pick) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl Eq for Pick {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GameData {
+    id: u32,
+    picks: Vec<Pick>,
+    /// The most cubes of each colour shown in any one pick.
+    maxima: Pick,
+}
+
+impl GameData {
+    fn new(id: u32, picks: Vec<Pick>) -> GameData {
+        let mut maxima = Pick::default();
+        for (colour, count) in picks.iter().flat_map(Pick::counts) {
+            let max = maxima.counts.entry(colour).or_insert(0);
+            *max = (*max).max(count);
+        }
+        GameData { id, picks, maxima }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn picks(&self) -> &[Pick] {
+        &self.picks
+    }
+
+    /// The fewest cubes of each colour the bag could have held.
+    pub fn maxima(&self) -> &Pick {
+        &self.maxima
+    }
+
+    pub fn max(&self, colour: Colour) -> u32 {
+        self.maxima.count(colour)
+    }
+}
+
+/// `3 blue, 4 red`, colours in name order.
+impl fmt::Display for Pick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (colour, count)) in self.counts().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{} {}", count, colour)?;
+        }
+        Ok(())
+    }
+}
+
+/// The canonical form of a game, `Game 1: 3 blue, 4 red; 2 green`, which
+/// `line_parser` reads back to the same `GameData`.
+impl fmt::Display for GameData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, pick) in self.picks.iter().enumerate() {
+            let separator = if i > 0 { ";" } else { "" };
+            write!(f, "{} {}", separator, pick)?;
+        }
+        Ok(())
+    }
+}
+
+/// One canonical line per game.
+pub fn format_games(games: &[GameData]) -> String {
+    games.iter().map(|game| format!("{}\n", game)).collect()
+}
+
+/// Every game of `input`, or the first line that is not one. Blank lines
+/// are ignored.
+pub fn parse_games(input: &str, repeats: RepeatedColours) -> Result<Vec<GameData>, ParseError> {
+    parse_lines(input, repeats).into_iter().collect()
+}
+
+/// The games of `input`, and the errors of the lines that were skipped.
+pub fn parse_games_lenient(
+    input: &str,
+    repeats: RepeatedColours,
+) -> (Vec<GameData>, Vec<ParseError>) {
+    let mut games = vec![];
+    let mut skipped = vec![];
+    for result in parse_lines(input, repeats) {
+        match result {
+            Ok(game) => games.push(game),
+            Err(e) => skipped.push(e),
+        }
+    }
+    (games, skipped)
+}
+
+/// Runs `line_parser` over each non-blank line, slicing them out of one span
+/// of the whole input so that errors carry their real line number.
+fn parse_lines(input: &str, repeats: RepeatedColours) -> Vec<Result<GameData, ParseError>> {
+    let mut results = vec![];
+    let mut rest = Span::new(input);
+
+    while !rest.fragment().is_empty() {
+        let fragment = *rest.fragment();
+        let end = fragment.find('\n').unwrap_or(fragment.len());
+        let line = rest.slice(..fragment[..end].trim_end_matches('\r').len());
+
+        if !line.fragment().trim().is_empty() {
+            let result = match line_parser(repeats, line) {
+                Ok((_, game)) => Ok(game),
+                Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(e),
+                Err(nom::Err::Incomplete(_)) => Err(ParseError::at(line, "a complete line")),
+            };
+            results.push(result);
+        }
+        rest = rest.slice((end + 1).min(fragment.len())..);
+    }
+
+    results
+}
+
+/// Sums the ids of the games `bag` could have produced.
+fn first_case(input: &[GameData], bag: &Bag) -> SolutionResult<u64> {
+    input
+        .iter()
+        .filter(|gd| bag.allows(gd))
+        .try_fold(0u64, |total, gd| total.checked_add(u64::from(gd.id)))
+        .ok_or_else(|| "the sum of game ids overflows u64".into())
+}
+
+/// Sums the power of every game: the product of its maxima over the colours
+/// of `bag`, so a bag colour the game never shows makes it 0. Taking the
+/// colours from the bag rather than from the input keeps each game's power
+/// independent of the other lines.
+fn second_case(input: &[GameData], bag: &Bag) -> SolutionResult<u64> {
+    let overflow = || "the sum of game powers overflows u64";
+    input.iter().try_fold(0u64, |total, gd| {
+        let power = bag
+            .counts()
+            .try_fold(1u64, |power, (c, _)| {
+                power.checked_mul(u64::from(gd.max(c)))
+            })
+            .ok_or_else(overflow)?;
+        Ok(total.checked_add(power).ok_or_else(overflow)?)
+    })
+}
+
+/// Turns a recoverable error from `parser` into a failure saying what was
+/// expected where it started.
+fn expect<'a, T>(
+    expected: &'static str,
+    mut parser: impl FnMut(Span<'a>) -> GameResult<'a, T>,
+) -> impl FnMut(Span<'a>) -> GameResult<'a, T> {
+    move |input| {
+        parser(input).map_err(|e| match e {
+            nom::Err::Error(_) => nom::Err::Failure(ParseError::at(input, expected)),
+            e => e,
+        })
+    }
+}
+
+fn number_parser(input: Span) -> GameResult<u32> {
+    map_res(digit1, |n: Span| n.fragment().parse::<u32>())(input)
+}
+
+fn game_id_parser(input: Span) -> GameResult<u32> {
+    let (rest, _) = expect("'Game'", tag("Game"))(input)?;
+    let (rest, _) = space0(rest)?;
+    let (rest, id) = expect("game id after 'Game'", number_parser)(rest)?;
+    let (rest, _) = expect("':' after game id", tag(":"))(rest)?;
+    Ok((rest, id))
+}
+
+/// `3 blue`, with any spaces before it, and where its count starts.
+fn cubes_parser(input: Span) -> GameResult<(Span, u32, Colour)> {
+    let (start, _) = space0(input)?;
+    let (rest, count) = expect("count", number_parser)(start)?;
+    let (rest, _) = space0(rest)?;
+    let (rest, colour) = expect("colour after count", alpha1)(rest)?;
+    Ok((rest, (start, count, Colour::new(colour.fragment()))))
+}
+
+/// `3 blue, 4 red`, up to the `;` or the end of the line.
+fn pick_parser<'a>(repeats: RepeatedColours, input: Span<'a>) -> GameResult<'a, Pick> {
+    let (rest, cubes) = separated_list1(pair(space0, tag(",")), cubes_parser)(input)?;
+    let (rest, _) = space0(rest)?;
+
+    let mut pick = Pick::default();
+    for (start, count, colour) in cubes {
+        let Some(&seen) = pick.counts.get(&colour) else {
+            pick.counts.insert(colour, count);
+            continue;
+        };
+        let merged = match repeats {
+            RepeatedColours::Error => {
+                return Err(nom::Err::Failure(ParseError::at(
+                    start,
+                    "each colour once per pick",
+                )))
+            }
+            RepeatedColours::Sum => seen.checked_add(count).ok_or_else(|| {
+                nom::Err::Failure(ParseError::at(start, "a total of at most 4294967295"))
+            })?,
+            RepeatedColours::Max => seen.max(count),
+        };
+        pick.counts.insert(colour, merged);
+        pick.merged = Some(repeats);
+    }
+    Ok((rest, pick))
+}
+
+fn repeated_pick_parser<'a>(
+    repeats: RepeatedColours,
+    input: Span<'a>,
+) -> GameResult<'a, Vec<Pick>> {
+    separated_list1(tag(";"), |i| pick_parser(repeats, i))(input)
+}
+
+fn line_parser<'a>(repeats: RepeatedColours, input: Span<'a>) -> GameResult<'a, GameData> {
+    let (rest, id) = game_id_parser(input)?;
+    let (rest, _) = space0(rest)?;
+    if rest.fragment().is_empty() {
+        return Ok((rest, GameData::new(id, vec![])));
+    }
+
+    let (rest, picks) = repeated_pick_parser(repeats, rest)?;
+    let (rest, _) = expect("',' or ';' after colour", eof)(rest)?;
+    Ok((rest, GameData::new(id, picks)))
+}
+
+#[cfg(test)]
+mod day2_tests {
+    use super::*;
+    use crate::solution::Runner;
+    use crate::testing::XorShift;
+
+    #[test]
+    fn test_game_id_parser() {
+        let (rest, id) = game_id_parser(Span::new("Game 1:")).unwrap();
+        assert_eq!((*rest.fragment(), id), ("", 1));
+    }
+
+    #[test]
+    fn test_pick_parser() {
+        let (rest, pick) =
+            pick_parser(RepeatedColours::Error, Span::new(" 3 blue, 4 red; 2 green")).unwrap();
+        assert_eq!(*rest.fragment(), "; 2 green");
+        assert_eq!(pick, Pick::new([("red", 4), ("blue", 3)]));
+    }
+
+    #[test]
+    fn test_repeat_pick_parser() {
+        let input = Span::new(" 3 blue, 4 red; 2 green; 1 red, 2 blue, 3 green");
+        let (rest, picks) = repeated_pick_parser(RepeatedColours::Error, input).unwrap();
+        assert_eq!(*rest.fragment(), "");
+        assert_eq!(
+            picks,
+            vec![
+                Pick::new([("red", 4), ("blue", 3)]),
+                Pick::new([("green", 2)]),
+                Pick::new([("red", 1), ("blue", 2), ("green", 3)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_parser() {
+        let (rest, game) = line_parser(
+            RepeatedColours::Error,
+            Span::new("Game 1: 3 blue, 4 red; 2 green"),
+        )
+        .unwrap();
+        assert_eq!(*rest.fragment(), "");
+        assert_eq!(game.id(), 1);
+        assert_eq!(
+            game.picks(),
+            [
+                Pick::new([("red", 4), ("blue", 3)]),
+                Pick::new([("green", 2)])
+            ]
+        );
+        assert_eq!(
+            game.maxima(),
+            &Pick::new([("red", 4), ("blue", 3), ("green", 2)])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_carry_their_location() {
+        let cases = [
+            ("Gmae 1: 3 red", "expected 'Game' at 1:1"),
+            ("Game one: 3 red", "expected game id after 'Game' at 1:6"),
+            ("Game 1 3 red", "expected ':' after game id at 1:7"),
+            ("Game 1: red", "expected count at 1:9"),
+            ("Game 1: 3 red;", "expected count at 1:15"),
+            ("Game 1: 3 red, 4", "expected colour after count at 1:17"),
+            (
+                "Game 1: 3 red 4 blue",
+                "expected ',' or ';' after colour at 1:15",
+            ),
+        ];
+        for (input, message) in cases {
+            let error = parse_games(input, RepeatedColours::Error).unwrap_err();
+            assert_eq!(error.to_string(), message, "{}", input);
+        }
+
+        let input = "Game 1: 3 blue\n\nGame 2: 4 red\r\nGame 3: 5 green, 4\nGame 4: 1 red";
+        let error = parse_games(input, RepeatedColours::Error).unwrap_err();
+        assert_eq!(error.to_string(), "expected colour after count at 4:19");
+    }
+
+    #[test]
+    fn test_lenient_parsing_skips_bad_lines() {
+        let input = "Game 1: 3 blue\nGame 2: 4 rd, \nGame 3: 2 red\nGame x";
+        let (games, skipped) = parse_games_lenient(input, RepeatedColours::Error);
+        let ids: Vec<u32> = games.iter().map(GameData::id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(
+            skipped,
+            vec![
+                ParseError {
+                    line: 2,
+                    column: 15,
+                    expected: "count"
+                },
+                ParseError {
+                    line: 4,
+                    column: 6,
+                    expected: "game id after 'Game'"
+                }
+            ]
+        );
+
+        let mut options = Options::default();
+        assert!(Day2::from_options(&options).unwrap().parse(input).is_err());
+        options.set("parse", "lenient");
+        let day = Day2::from_options(&options).unwrap();
+        assert_eq!(day.parse(input).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_lenient_runs_report_the_skipped_lines() {
+        let input = "Game 1: 3 blue\nGame 2: 4 rd, \n\nGame 3: 2 red\nGame x";
+        let day = Day2 {
+            mode: ParseMode::Lenient,
+            ..Day2::default()
+        };
+
+        let (games, skipped) = day.parse_with_skipped(input).unwrap();
+        assert_eq!(games.len(), 2);
+        assert_eq!(
+            skipped,
+            vec![
+                "expected count at 2:15",
+                "expected game id after 'Game' at 5:6"
+            ]
+        );
+
+        let report = day.run(input, &[1]).unwrap();
+        assert_eq!(report.skipped, skipped);
+        assert!(Day2::default().parse_with_skipped(input).is_err());
+    }
+
+    #[test]
+    fn test_repeated_colours_follow_the_policy() {
+        let input = "Game 1: 3 red, 2 blue; 3 green, 3 red, 4 red";
+
+        let error = parse_games(input, RepeatedColours::Error).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected each colour once per pick at 1:40"
+        );
+
+        let games = parse_games(input, RepeatedColours::Sum).unwrap();
+        let picks = games[0].picks();
+        assert_eq!(picks[0].merged(), None);
+        assert_eq!(picks[1].merged(), Some(RepeatedColours::Sum));
+        assert_eq!(picks[1].count(Colour::new("red")), 7);
+        assert_eq!(games[0].max(Colour::new("red")), 7);
+
+        let games = parse_games(input, RepeatedColours::Max).unwrap();
+        let picks = games[0].picks();
+        assert_eq!(picks[1].merged(), Some(RepeatedColours::Max));
+        assert_eq!(picks[1].count(Colour::new("red")), 4);
+        assert_eq!(picks[1].count(Colour::new("green")), 3);
+
+        let mut options = Options::default();
+        options.set("repeats", "sum");
+        let day = Day2::from_options(&options).unwrap();
+        assert_eq!(day.part2(&day.parse(input).unwrap()).unwrap(), 7 * 2 * 3);
+        assert!("last".parse::<RepeatedColours>().is_err());
+
+        let huge = "Game 1: 4000000000 red, 4000000000 red";
+        assert_eq!(
+            parse_games(huge, RepeatedColours::Sum)
+                .unwrap_err()
+                .to_string(),
+            "expected a total of at most 4294967295 at 1:25"
+        );
+        let games = parse_games(huge, RepeatedColours::Max).unwrap();
+        assert_eq!(games[0].max(Colour::new("red")), 4_000_000_000);
+    }
+
+    #[test]
+    fn test_any_colour_parses() {
+        let (_, game) = line_parser(
+            RepeatedColours::Error,
+            Span::new("Game 7: 2 yellow, 1 red; 5 purple, 3 yellow"),
+        )
+        .unwrap();
+        assert_eq!(game.max(Colour::new("yellow")), 3);
+        assert_eq!(game.max(Colour::new("purple")), 5);
+        assert_eq!(game.max(Colour::new("green")), 0);
+
+        let input = parse_games(
+            "Game 1: 2 yellow, 1 red\nGame 2: 4 yellow; 3 red, 2 purple",
+            RepeatedColours::Error,
+        )
+        .unwrap();
+        assert_eq!(
+            first_case(&input, &Bag::new([("yellow", 3), ("red", 3)])).unwrap(),
+            1
+        );
+        // Game 1 shows no purple, so its power is 0.
+        let bag = Bag::new([("yellow", 3), ("red", 3), ("purple", 3)]);
+        assert_eq!(second_case(&input, &bag).unwrap(), 4 * 3 * 2);
+    }
+
+    #[test]
+    fn test_first_case() {
+        let input = parse_games(
+            "Game 1: 3 blue, 4 red; 2 green\nGame 2: 100 blue; 4 red",
+            RepeatedColours::Error,
+        )
+        .unwrap();
+        let result = first_case(&input, &Bag::new([("red", 5), ("green", 5), ("blue", 5)]));
+        assert_eq!(result.unwrap(), 1);
+
+        let input = parse_games(
+            "Game 4000000000: 1 red\nGame 4000000000: 1 red",
+            RepeatedColours::Error,
+        )
+        .unwrap();
+        assert_eq!(first_case(&input, &Bag::default()).unwrap(), 8_000_000_000);
+    }
+
+    #[test]
+    fn test_bag_from_options() {
+        let input = parse_games(
+            "Game 1: 3 blue, 4 red\nGame 2: 13 red",
+            RepeatedColours::Error,
+        )
+        .unwrap();
+        let mut options = Options::default();
+        assert_eq!(
+            Day2::from_options(&options).unwrap().part1(&input).unwrap(),
+            1
+        );
+
+        options.set("bag", "red=13,blue=3");
+        assert_eq!(
+            Day2::from_options(&options).unwrap().part1(&input).unwrap(),
+            3
+        );
+
+        let path = std::env::temp_dir().join(format!("aoc-bag-{}.txt", std::process::id()));
+        std::fs::write(&path, "red = 4 # only four\nblue = 3\n").unwrap();
+        options.set("bag-file", path.to_str().unwrap());
+        assert!(Day2::from_options(&options).is_err());
+
+        let mut options = Options::default();
+        options.set("bag-file", path.to_str().unwrap());
+        let day = Day2::from_options(&options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(day.bag, Bag::new([("red", 4), ("blue", 3)]));
+        assert_eq!(day.part1(&input).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_format_is_canonical() {
+        let games = parse_games(
+            "Game  3:3 red ,4 blue;1 green\n\nGame 4:\nGame 5: 2 red;0 blue",
+            RepeatedColours::Error,
+        )
+        .unwrap();
+        assert_eq!(
+            format_games(&games),
+            "Game 3: 4 blue, 3 red; 1 green\nGame 4:\nGame 5: 2 red; 0 blue\n"
+        );
+        assert_eq!(
+            parse_games(&format_games(&games), RepeatedColours::Error).unwrap(),
+            games
+        );
+    }
+
+    #[test]
+    fn test_format_round_trips_generated_games() {
+        let mut rng = XorShift::new(0x0da7_2002);
+        let mut below = |n: usize| rng.below(n);
+        let palette = ["red", "green", "blue", "yellow", "purple", "teal"];
+
+        for _ in 0..500 {
+            let picks: Vec<Pick> = (0..below(6))
+                .map(|_| {
+                    let colours = 1 + below(palette.len());
+                    Pick::new((0..colours).map(|_| {
+                        let colour = palette[below(palette.len())];
+                        (colour, below(30) as u32)
+                    }))
+                })
+                .collect();
+            let game = GameData::new(below(1000) as u32, picks);
+
+            let line = game.to_string();
+            let (rest, parsed) = line_parser(RepeatedColours::Error, Span::new(&line)).unwrap();
+            assert_eq!(*rest.fragment(), "");
+            assert_eq!(parsed, game, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_format_round_trips_merged_picks() {
+        let line = "Game 1: 3 red, 2 blue; 3 green, 3 red, 4 red";
+        for repeats in [RepeatedColours::Sum, RepeatedColours::Max] {
+            let (_, game) = line_parser(repeats, Span::new(line)).unwrap();
+            assert_eq!(game.picks()[1].merged(), Some(repeats));
+
+            let formatted = game.to_string();
+            let (rest, parsed) = line_parser(repeats, Span::new(&formatted)).unwrap();
+            assert_eq!(*rest.fragment(), "");
+            assert_eq!(parsed.picks()[1].merged(), None);
+            assert_eq!(parsed, game, "{}", formatted);
+        }
+    }
+
+    #[test]
+    fn test_second_case() {
+        let input = parse_games(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+            RepeatedColours::Error,
+        )
+        .unwrap();
+        let result = second_case(&input, &Bag::default()).unwrap();
+        assert_eq!(result, 2286);
+    }
+
+    #[test]
+    fn test_second_case_powers_stand_alone() {
+        let alone = parse_games("Game 1: 3 blue, 4 red, 1 green", RepeatedColours::Error);
+        let with_purple = parse_games(
+            "Game 1: 3 blue, 4 red, 1 green\nGame 2: 1 purple",
+            RepeatedColours::Error,
+        );
+        let bag = Bag::default();
+        assert_eq!(second_case(&alone.unwrap(), &bag).unwrap(), 12);
+        assert_eq!(second_case(&with_purple.unwrap(), &bag).unwrap(), 12);
+
+        let huge = parse_games(
+            "Game 1: 70000 red, 70000 blue, 70000 green",
+            RepeatedColours::Error,
+        );
+        assert_eq!(second_case(&huge.unwrap(), &bag).unwrap(), 70000u64.pow(3));
+
+        let max = u32::MAX;
+        let line = format!("Game 1: {max} red, {max} blue, {max} green\nGame 2: {max} red, {max} blue, {max} green");
+        let games = parse_games(&line, RepeatedColours::Error).unwrap();
+        assert!(second_case(&games, &bag).is_err());
+    }
+}
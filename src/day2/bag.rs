@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Colour, GameData};
+
+/// How many cubes of each colour the bag holds. A colour that is not listed
+/// holds none.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Bag {
+    counts: BTreeMap<Colour, u32>,
+}
+
+/// A pick that shows more cubes of a colour than the bag holds.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Violation {
+    /// 1-based.
+    pub pick: usize,
+    pub colour: Colour,
+    pub shown: u32,
+    pub available: u32,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pick {} shows {} {} but the bag holds {}",
+            self.pick, self.shown, self.colour, self.available
+        )
+    }
+}
+
+/// A game the bag could not have produced, and every reason why.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Infeasible<'a> {
+    pub game: &'a GameData,
+    pub violations: Vec<Violation>,
+}
+
+impl Bag {
+    pub fn new<'a>(counts: impl IntoIterator<Item = (&'a str, u32)>) -> Bag {
+        Bag {
+            counts: counts
+                .into_iter()
+                .map(|(name, count)| (Colour::new(name), count))
+                .collect(),
+        }
+    }
+
+    pub fn count(&self, colour: Colour) -> u32 {
+        self.counts.get(&colour).copied().unwrap_or(0)
+    }
+
+    pub fn counts(&self) -> impl Iterator<Item = (Colour, u32)> + '_ {
+        self.counts.iter().map(|(&colour, &count)| (colour, count))
+    }
+
+    pub fn allows(&self, game: &GameData) -> bool {
+        game.maxima().counts().all(|(c, max)| max <= self.count(c))
+    }
+
+    /// Every colour of every pick of `game` that the bag cannot supply.
+    pub fn violations(&self, game: &GameData) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (index, pick) in game.picks().iter().enumerate() {
+            for (colour, shown) in pick.counts() {
+                let available = self.count(colour);
+                if shown > available {
+                    violations.push(Violation {
+                        pick: index + 1,
+                        colour,
+                        shown,
+                        available,
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// The games of `games` the bag could not have produced, in input order.
+    pub fn infeasible<'a>(&self, games: &'a [GameData]) -> Vec<Infeasible<'a>> {
+        games
+            .iter()
+            .filter_map(|game| {
+                let violations = self.violations(game);
+                if violations.is_empty() {
+                    None
+                } else {
+                    Some(Infeasible { game, violations })
+                }
+            })
+            .collect()
+    }
+}
+
+/// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+impl Default for Bag {
+    fn default() -> Self {
+        Bag::new([("red", 12), ("green", 13), ("blue", 14)])
+    }
+}
+
+/// `blue=14,green=13,red=12`, which `--bag` reads back.
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (colour, count)) in self.counts().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={}", colour, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Accepts `colour=count` entries separated by commas or newlines, such as
+/// `red=12,green=13,blue=14`. In a file, `#` starts a comment.
+impl FromStr for Bag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut counts = BTreeMap::new();
+        let entries = s
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or(""))
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty());
+
+        for entry in entries {
+            let (colour, count) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected colour=count, got '{}'", entry))?;
+            let colour = colour.trim();
+            // Games only ever name colours made of ASCII letters.
+            if colour.is_empty() || !colour.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(format!("'{}' is not a colour name in '{}'", colour, entry));
+            }
+            let count = count
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("'{}' is not a valid count for {}", count.trim(), colour))?;
+            if counts.insert(Colour::new(colour), count).is_some() {
+                return Err(format!("{} is given more than once", colour));
+            }
+        }
+
+        Ok(Bag { counts })
+    }
+}
+
+#[cfg(test)]
+mod bag_tests {
+    use super::super::{parse_games, RepeatedColours};
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "red=12, green=13,blue=14".parse::<Bag>(),
+            Ok(Bag::default())
+        );
+        assert_eq!(
+            "# the puzzle bag\nred = 12\ngreen = 13 # lucky\n\nblue = 14\n".parse::<Bag>(),
+            Ok(Bag::default())
+        );
+        assert!("red=12,red=3".parse::<Bag>().is_err());
+        assert!("red".parse::<Bag>().is_err());
+        assert!("red=many".parse::<Bag>().is_err());
+        assert_eq!(
+            "=5".parse::<Bag>(),
+            Err(String::from("'' is not a colour name in '=5'"))
+        );
+        assert_eq!(
+            "dark red=3".parse::<Bag>(),
+            Err(String::from(
+                "'dark red' is not a colour name in 'dark red=3'"
+            ))
+        );
+        assert_eq!(Bag::default().to_string(), "blue=14,green=13,red=12");
+    }
+
+    #[test]
+    fn test_infeasible_reports_colour_and_pick() {
+        let games = parse_games(
+            "Game 1: 3 blue, 4 red; 2 green
+Game 2: 1 red; 20 red, 15 blue; 14 blue
+Game 3: 1 purple",
+            RepeatedColours::Error,
+        )
+        .unwrap();
+        let bag = Bag::default();
+        assert!(bag.allows(&games[0]));
+
+        let infeasible = bag.infeasible(&games);
+        let ids: Vec<u32> = infeasible.iter().map(|i| i.game.id()).collect();
+        assert_eq!(ids, vec![2, 3]);
+        assert_eq!(
+            infeasible[0].violations,
+            vec![
+                Violation {
+                    pick: 2,
+                    colour: Colour::new("blue"),
+                    shown: 15,
+                    available: 14
+                },
+                Violation {
+                    pick: 2,
+                    colour: Colour::new("red"),
+                    shown: 20,
+                    available: 12
+                }
+            ]
+        );
+        assert_eq!(
+            infeasible[1].violations[0].to_string(),
+            "pick 1 shows 1 purple but the bag holds 0"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+/// An interned colour name, stored once for the life of the program so
+/// copies are free. Comparisons compare the names as strings, which keeps
+/// colours in alphabetical order.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Colour(&'static str);
+
+impl Colour {
+    pub fn new(name: &str) -> Colour {
+        static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+        let mut names = NAMES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        match names.get(name) {
+            Some(&interned) => Colour(interned),
+            None => {
+                let interned: &'static str = Box::leak(name.into());
+                names.insert(interned);
+                Colour(interned)
+            }
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[cfg(test)]
+mod colour_tests {
+    use super::*;
+
+    #[test]
+    fn test_interning() {
+        let red = Colour::new("red");
+        let again = Colour::new(&String::from("red"));
+        assert_eq!(red, again);
+        assert!(std::ptr::eq(red.name(), again.name()));
+        assert_ne!(red, Colour::new("purple"));
+        assert_eq!(Colour::new("purple").to_string(), "purple");
+    }
+}
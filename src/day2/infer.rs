@@ -0,0 +1,267 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+
+use crate::hypergeometric::ln_multivariate_hypergeometric;
+
+use super::{Bag, Colour, GameData, Pick};
+
+/// How many cubes of each colour `infer` tries at most, unless a game
+/// already shows more.
+pub const DEFAULT_LIMIT: u32 = 20;
+
+/// The most candidate bags `candidate_bags` will build for one search.
+pub const MAX_CANDIDATES: u64 = 1_000_000;
+
+/// Log-likelihoods this close count as a tie, which the smaller bag wins.
+const TIE: f64 = 1e-9;
+
+/// A bag together with the log-likelihood of the games it was fitted to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub bag: Bag,
+    pub log_likelihood: f64,
+}
+
+/// The most likely bag for each game on its own and for all games at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inference {
+    pub games: Vec<(u32, Estimate)>,
+    pub overall: Option<Estimate>,
+}
+
+/// Trying `limit` cubes of each of `colours` colours would mean more than
+/// `MAX_CANDIDATES` bags.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TooManyCandidates {
+    pub colours: usize,
+    pub limit: u32,
+}
+
+impl fmt::Display for TooManyCandidates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "up to {} cubes of {} colours is more than {} candidate bags",
+            self.limit, self.colours, MAX_CANDIDATES
+        )
+    }
+}
+
+impl Error for TooManyCandidates {}
+
+/// `ln P(pick | bag)` when the cubes of a pick are drawn together, without
+/// replacement, from the full bag.
+pub fn pick_log_likelihood(bag: &Bag, pick: &Pick) -> f64 {
+    let colours: BTreeSet<Colour> = bag
+        .counts()
+        .chain(pick.counts())
+        .map(|(colour, _)| colour)
+        .collect();
+    let held: Vec<u32> = colours.iter().map(|&c| bag.count(c)).collect();
+    let drawn: Vec<u32> = colours.iter().map(|&c| pick.count(c)).collect();
+    ln_multivariate_hypergeometric(&held, &drawn)
+}
+
+/// `ln P(game | bag)`. The cubes go back in the bag after every pick, so the
+/// picks are independent and their log-likelihoods add up.
+pub fn game_log_likelihood(bag: &Bag, game: &GameData) -> f64 {
+    game.picks()
+        .iter()
+        .map(|pick| pick_log_likelihood(bag, pick))
+        .sum()
+}
+
+/// Every bag holding, of each listed colour, from its minimum up to `limit`
+/// cubes (or exactly the minimum, if that is already above `limit`), as long
+/// as there are at most `MAX_CANDIDATES` of them.
+pub fn candidate_bags(
+    minimum: &[(Colour, u32)],
+    limit: u32,
+) -> Result<Vec<Bag>, TooManyCandidates> {
+    let count = minimum.iter().try_fold(1u64, |count, &(_, least)| {
+        count.checked_mul(u64::from(limit.saturating_sub(least)) + 1)
+    });
+    if count.is_none_or(|count| count > MAX_CANDIDATES) {
+        return Err(TooManyCandidates {
+            colours: minimum.len(),
+            limit,
+        });
+    }
+
+    let mut bags = vec![vec![]];
+    for &(colour, least) in minimum {
+        bags = bags
+            .into_iter()
+            .flat_map(|counts: Vec<(Colour, u32)>| {
+                (least..=limit.max(least)).map(move |count| {
+                    let mut counts = counts.clone();
+                    counts.push((colour, count));
+                    counts
+                })
+            })
+            .collect();
+    }
+
+    Ok(bags
+        .into_iter()
+        .map(|counts| Bag::new(counts.into_iter().map(|(c, n)| (c.name(), n))))
+        .collect())
+}
+
+/// The candidate under which `games` are most likely, preferring the bag
+/// with fewer cubes on a tie.
+pub fn most_likely_bag(games: &[GameData], candidates: &[Bag]) -> Option<Estimate> {
+    let size = |bag: &Bag| bag.counts().map(|(_, n)| u64::from(n)).sum::<u64>();
+    let mut best: Option<Estimate> = None;
+
+    for bag in candidates {
+        let log_likelihood: f64 = games.iter().map(|g| game_log_likelihood(bag, g)).sum();
+        let better = best.as_ref().is_none_or(|b| {
+            log_likelihood > b.log_likelihood + TIE
+                || (log_likelihood > b.log_likelihood - TIE && size(bag) < size(&b.bag))
+        });
+        if better {
+            best = Some(Estimate {
+                bag: bag.clone(),
+                log_likelihood,
+            });
+        }
+    }
+
+    best
+}
+
+/// Fits a bag to each game and one to all of them, trying every bag from
+/// the fewest cubes the games allow up to `limit` of each colour seen. The
+/// search is exponential in the number of colours, so it gives up past
+/// `MAX_CANDIDATES` bags.
+pub fn infer(games: &[GameData], limit: u32) -> Result<Inference, TooManyCandidates> {
+    let colours: BTreeSet<Colour> = games
+        .iter()
+        .flat_map(|g| g.maxima().counts())
+        .map(|(colour, _)| colour)
+        .collect();
+    let minimum = |games: &[GameData]| -> Vec<(Colour, u32)> {
+        colours
+            .iter()
+            .map(|&c| (c, games.iter().map(|g| g.max(c)).max().unwrap_or(0)))
+            .collect()
+    };
+
+    let mut per_game = vec![];
+    for game in games {
+        let games = std::slice::from_ref(game);
+        let candidates = candidate_bags(&minimum(games), limit)?;
+        if let Some(estimate) = most_likely_bag(games, &candidates) {
+            per_game.push((game.id(), estimate));
+        }
+    }
+    let overall = most_likely_bag(games, &candidate_bags(&minimum(games), limit)?);
+
+    Ok(Inference {
+        games: per_game,
+        overall,
+    })
+}
+
+#[cfg(test)]
+mod infer_tests {
+    use super::super::{parse_games, RepeatedColours};
+    use super::*;
+
+    fn games(input: &str) -> Vec<GameData> {
+        parse_games(input, RepeatedColours::Error).unwrap()
+    }
+
+    #[test]
+    fn test_pick_log_likelihood() {
+        let bag = Bag::new([("red", 2), ("blue", 3), ("green", 5)]);
+        let pick = Pick::new([("red", 1), ("blue", 1), ("green", 1)]);
+        assert!((pick_log_likelihood(&bag, &pick).exp() - 0.25).abs() < 1e-9);
+
+        let unseen = Pick::new([("purple", 1)]);
+        assert_eq!(pick_log_likelihood(&bag, &unseen), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_candidate_bags() {
+        let red = Colour::new("red");
+        let blue = Colour::new("blue");
+        let bags = candidate_bags(&[(red, 1), (blue, 4)], 3).unwrap();
+        assert_eq!(
+            bags,
+            vec![
+                Bag::new([("red", 1), ("blue", 4)]),
+                Bag::new([("red", 2), ("blue", 4)]),
+                Bag::new([("red", 3), ("blue", 4)]),
+            ]
+        );
+
+        let six: Vec<(Colour, u32)> = ["a", "b", "c", "d", "e", "f"]
+            .into_iter()
+            .map(|name| (Colour::new(name), 0))
+            .collect();
+        assert_eq!(
+            candidate_bags(&six, 20),
+            Err(TooManyCandidates {
+                colours: 6,
+                limit: 20
+            })
+        );
+        assert!(candidate_bags(&six, u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_infer() {
+        let games =
+            games("Game 1: 2 red; 2 red\nGame 2: 1 red, 1 blue\nGame 3: 2 red, 2 blue; 4 red");
+        let inference = infer(&games, 6).unwrap();
+
+        // Any bag of only red cubes explains game 1 for certain, and the
+        // smallest wins; game 2 needs exactly one of each to be certain.
+        assert_eq!(
+            inference.games[0].1.bag,
+            Bag::new([("red", 2), ("blue", 0)])
+        );
+        assert_eq!(inference.games[0].1.log_likelihood, 0.0);
+        assert_eq!(
+            inference.games[1].1.bag,
+            Bag::new([("red", 1), ("blue", 1)])
+        );
+
+        let overall = inference.overall.unwrap();
+        let total: f64 = games
+            .iter()
+            .map(|g| game_log_likelihood(&overall.bag, g))
+            .sum();
+        assert_eq!(overall.log_likelihood, total);
+        let candidates = [(Colour::new("blue"), 2), (Colour::new("red"), 4)];
+        for bag in candidate_bags(&candidates, 6).unwrap() {
+            let likelihood: f64 = games.iter().map(|g| game_log_likelihood(&bag, g)).sum();
+            assert!(likelihood <= overall.log_likelihood + TIE);
+        }
+
+        let colourful = parse_games(
+            "Game 1: 1 a, 1 b, 1 c, 1 d, 1 e, 1 f",
+            RepeatedColours::Error,
+        );
+        assert!(infer(&colourful.unwrap(), 20).is_err());
+    }
+
+    #[test]
+    fn test_infer_with_huge_counts() {
+        let huge = games("Game 1: 4000000000 red, 4000000000 blue");
+        let inference = infer(&huge, 20).unwrap();
+        assert_eq!(
+            inference.overall.unwrap().bag,
+            Bag::new([("red", 4_000_000_000), ("blue", 4_000_000_000)])
+        );
+
+        // Half the red cubes in one pick: a large draw from a large bag.
+        let half = games("Game 1: 100000000 red, 1 blue; 50000000 red");
+        let overall = infer(&half, 20).unwrap().overall.unwrap();
+        assert_eq!(overall.bag, Bag::new([("red", 100_000_000), ("blue", 1)]));
+        assert!((overall.log_likelihood - 0.5f64.ln()).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,332 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, char, digit1, multispace0};
+use nom::combinator::{all_consuming, cut, map, map_res, verify};
+use nom::multi::many0;
+use nom::sequence::{delimited, pair, preceded, tuple};
+use nom::IResult;
+
+use super::{Colour, GameData, Pick};
+
+const KEYWORDS: [&str; 8] = ["and", "or", "not", "any", "all", "id", "picks", "max"];
+
+/// A filter over games, such as `any(red > 12 and green < 3)` or
+/// `picks > 4 or not max.blue <= 6`.
+///
+/// Comparisons are between numbers and the values `id`, `picks` (how many
+/// picks the game has) and `max.<colour>` (the most of that colour in one
+/// pick). Inside `any(...)` or `all(...)` a bare colour name is its count in
+/// the pick at hand. `not` binds tighter than `and`, and `and` than `or`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Query {
+    Compare(Value, Op, Value),
+    Not(Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Any(Box<Query>),
+    All(Box<Query>),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Value {
+    Number(u32),
+    Id,
+    Picks,
+    Max(Colour),
+    /// Only inside a quantifier.
+    Count(Colour),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Op {
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Ge,
+    Gt,
+}
+
+/// Whether bare colour names are allowed, i.e. whether there is a pick.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Scope {
+    Game,
+    Pick,
+}
+
+impl Query {
+    pub fn matches(&self, game: &GameData) -> bool {
+        self.evaluate(game, None)
+    }
+
+    fn evaluate(&self, game: &GameData, pick: Option<&Pick>) -> bool {
+        match self {
+            Query::Compare(left, op, right) => {
+                match (left.resolve(game, pick), right.resolve(game, pick)) {
+                    (Some(l), Some(r)) => op.holds(l, r),
+                    _ => false,
+                }
+            }
+            Query::Not(query) => !query.evaluate(game, pick),
+            Query::And(l, r) => l.evaluate(game, pick) && r.evaluate(game, pick),
+            Query::Or(l, r) => l.evaluate(game, pick) || r.evaluate(game, pick),
+            Query::Any(query) => game.picks().iter().any(|p| query.evaluate(game, Some(p))),
+            Query::All(query) => game.picks().iter().all(|p| query.evaluate(game, Some(p))),
+        }
+    }
+}
+
+impl Value {
+    fn resolve(&self, game: &GameData, pick: Option<&Pick>) -> Option<u32> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Id => Some(game.id()),
+            Value::Picks => u32::try_from(game.picks().len()).ok(),
+            Value::Max(colour) => Some(game.max(*colour)),
+            Value::Count(colour) => pick.map(|p| p.count(*colour)),
+        }
+    }
+}
+
+impl Op {
+    fn holds(&self, left: u32, right: u32) -> bool {
+        match self {
+            Op::Lt => left < right,
+            Op::Le => left <= right,
+            Op::Eq => left == right,
+            Op::Ne => left != right,
+            Op::Ge => left >= right,
+            Op::Gt => left > right,
+        }
+    }
+}
+
+/// The ids of the games `query` matches, in input order.
+pub fn select(games: &[GameData], query: &Query) -> Vec<u32> {
+    games
+        .iter()
+        .filter(|game| query.matches(game))
+        .map(GameData::id)
+        .collect()
+}
+
+fn keyword<'a>(word: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    delimited(
+        multispace0,
+        verify(alpha1, move |w: &str| w == word),
+        multispace0,
+    )
+}
+
+fn symbol<'a>(c: char) -> impl FnMut(&'a str) -> IResult<&'a str, char> {
+    delimited(multispace0, char(c), multispace0)
+}
+
+fn colour_parser(input: &str) -> IResult<&str, Colour> {
+    map(
+        verify(alpha1, |w: &str| !KEYWORDS.contains(&w)),
+        Colour::new,
+    )(input)
+}
+
+fn value_parser(scope: Scope, input: &str) -> IResult<&str, Value> {
+    delimited(
+        multispace0,
+        alt((
+            map_res(digit1, |n: &str| n.parse().map(Value::Number)),
+            map(preceded(tag("max."), colour_parser), Value::Max),
+            map(verify(alpha1, |w: &str| w == "id"), |_| Value::Id),
+            map(verify(alpha1, |w: &str| w == "picks"), |_| Value::Picks),
+            map(
+                verify(colour_parser, |_| scope == Scope::Pick),
+                Value::Count,
+            ),
+        )),
+        multispace0,
+    )(input)
+}
+
+fn op_parser(input: &str) -> IResult<&str, Op> {
+    alt((
+        map(tag("<="), |_| Op::Le),
+        map(tag(">="), |_| Op::Ge),
+        map(tag("=="), |_| Op::Eq),
+        map(tag("!="), |_| Op::Ne),
+        map(tag("<"), |_| Op::Lt),
+        map(tag(">"), |_| Op::Gt),
+        map(tag("="), |_| Op::Eq),
+    ))(input)
+}
+
+fn comparison_parser(scope: Scope, input: &str) -> IResult<&str, Query> {
+    map(
+        tuple((
+            |i| value_parser(scope, i),
+            op_parser,
+            cut(|i| value_parser(scope, i)),
+        )),
+        |(left, op, right)| Query::Compare(left, op, right),
+    )(input)
+}
+
+/// `any(...)` or `all(...)`, which may not nest.
+fn quantifier_parser(scope: Scope, input: &str) -> IResult<&str, Query> {
+    let body = |i| {
+        cut(delimited(
+            symbol('('),
+            |i| or_parser(Scope::Pick, i),
+            symbol(')'),
+        ))(i)
+    };
+    verify(
+        alt((
+            map(preceded(keyword("any"), body), |q| Query::Any(Box::new(q))),
+            map(preceded(keyword("all"), body), |q| Query::All(Box::new(q))),
+        )),
+        |_| scope == Scope::Game,
+    )(input)
+}
+
+fn unary_parser(scope: Scope, input: &str) -> IResult<&str, Query> {
+    alt((
+        map(
+            preceded(keyword("not"), cut(|i| unary_parser(scope, i))),
+            |q| Query::Not(Box::new(q)),
+        ),
+        |i| quantifier_parser(scope, i),
+        delimited(symbol('('), cut(|i| or_parser(scope, i)), cut(symbol(')'))),
+        |i| comparison_parser(scope, i),
+    ))(input)
+}
+
+fn and_parser(scope: Scope, input: &str) -> IResult<&str, Query> {
+    map(
+        pair(
+            |i| unary_parser(scope, i),
+            many0(preceded(keyword("and"), cut(|i| unary_parser(scope, i)))),
+        ),
+        |(first, rest)| {
+            rest.into_iter()
+                .fold(first, |l, r| Query::And(Box::new(l), Box::new(r)))
+        },
+    )(input)
+}
+
+fn or_parser(scope: Scope, input: &str) -> IResult<&str, Query> {
+    map(
+        pair(
+            |i| and_parser(scope, i),
+            many0(preceded(keyword("or"), cut(|i| and_parser(scope, i)))),
+        ),
+        |(first, rest)| {
+            rest.into_iter()
+                .fold(first, |l, r| Query::Or(Box::new(l), Box::new(r)))
+        },
+    )(input)
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match all_consuming(|i| or_parser(Scope::Game, i))(s) {
+            Ok((_, query)) => Ok(query),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(QueryError {
+                column: s.len() - e.input.len() + 1,
+            }),
+            Err(nom::Err::Incomplete(_)) => Err(QueryError {
+                column: s.len() + 1,
+            }),
+        }
+    }
+}
+
+/// Where a query stopped making sense, as a 1-based byte column.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct QueryError {
+    pub column: usize,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot parse query at column {}", self.column)
+    }
+}
+
+impl Error for QueryError {}
+
+#[cfg(test)]
+mod query_tests {
+    use super::super::{parse_games, RepeatedColours};
+    use super::*;
+
+    const GAMES: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn ids(query: &str) -> Vec<u32> {
+        let games = parse_games(GAMES, RepeatedColours::Error).unwrap();
+        select(&games, &query.parse().unwrap())
+    }
+
+    #[test]
+    fn test_parse() {
+        let query: Query = "any(red > 12 and green < 3)".parse().unwrap();
+        let red = Colour::new("red");
+        let green = Colour::new("green");
+        assert_eq!(
+            query,
+            Query::Any(Box::new(Query::And(
+                Box::new(Query::Compare(Value::Count(red), Op::Gt, Value::Number(12))),
+                Box::new(Query::Compare(
+                    Value::Count(green),
+                    Op::Lt,
+                    Value::Number(3)
+                ))
+            )))
+        );
+
+        let query: Query = "not id = 1 or picks>=2".parse().unwrap();
+        assert!(matches!(query, Query::Or(l, _) if matches!(*l, Query::Not(_))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // A bare colour needs a pick to count in.
+        assert_eq!("red > 1".parse::<Query>(), Err(QueryError { column: 1 }));
+        assert_eq!(
+            "any(all(red > 1))".parse::<Query>(),
+            Err(QueryError { column: 5 })
+        );
+        assert_eq!(
+            "picks > 2 and".parse::<Query>(),
+            Err(QueryError { column: 14 })
+        );
+        assert_eq!(
+            "any(red > 1 or green >)".parse::<Query>(),
+            Err(QueryError { column: 23 })
+        );
+        assert_eq!(
+            "?".parse::<Query>().unwrap_err().to_string(),
+            "cannot parse query at column 1"
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(ids("any(red > 12)"), vec![3, 4]);
+        assert_eq!(ids("any(red > 12 and green > 5)"), vec![3]);
+        assert_eq!(ids("picks > 2"), vec![1, 2, 3, 4]);
+        assert_eq!(ids("max.blue <= 6 and not id = 1"), vec![2, 3, 5]);
+        assert_eq!(ids("all(green >= 1)"), vec![2, 3, 4, 5]);
+        assert_eq!(ids("id = 1 or (id = 2 or id = 5)"), vec![1, 2, 5]);
+        assert_eq!(ids("any(purple > 0)"), vec![]);
+        assert_eq!(ids("max.purple = 0"), vec![1, 2, 3, 4, 5]);
+    }
+}
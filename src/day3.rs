@@ -0,0 +1,477 @@
+use nom::branch::alt;
+use nom::bytes::complete::{take_while, take_while1};
+use nom::character::complete::{digit1, line_ending};
+use nom::combinator::eof;
+use nom::multi::many_till;
+use nom::sequence::preceded;
+use nom::IResult;
+use nom_locate::LocatedSpan;
+
+use crate::grid::{Grid, Position};
+use crate::solution::{Solution, SolutionResult};
+
+mod index;
+mod render;
+
+pub use index::AdjacencyIndex;
+pub use render::{render_ansi, render_html};
+
+type Span<'a> = LocatedSpan<&'a str>;
+
+#[derive(Debug, Clone, Default)]
+pub struct Day3 {
+    pub config: SchematicConfig,
+}
+
+/// Decides which non-digit cells count as symbols.
+#[derive(Debug, Clone, Default)]
+pub enum SymbolPolicy {
+    /// Anything that is neither a digit nor the blank cell.
+    #[default]
+    AnyNonBlank,
+    /// Only the listed characters; anything else is treated like a blank.
+    OneOf(String),
+    Custom(fn(char) -> bool),
+}
+
+#[derive(Debug, Clone)]
+pub struct SchematicConfig {
+    pub blank: char,
+    pub symbols: SymbolPolicy,
+    /// How far from a number a symbol may sit and still count as adjacent.
+    pub radius: usize,
+}
+
+impl Default for SchematicConfig {
+    fn default() -> Self {
+        SchematicConfig {
+            blank: '.',
+            symbols: SymbolPolicy::AnyNonBlank,
+            radius: 1,
+        }
+    }
+}
+
+impl SchematicConfig {
+    pub fn is_symbol(&self, c: char) -> bool {
+        if c.is_ascii_digit() || c == self.blank || c == '\n' || c == '\r' {
+            return false;
+        }
+
+        match &self.symbols {
+            SymbolPolicy::AnyNonBlank => true,
+            SymbolPolicy::OneOf(symbols) => symbols.contains(c),
+            SymbolPolicy::Custom(f) => f(c),
+        }
+    }
+
+    /// Cells that are neither digits, blanks nor symbols under this config.
+    fn is_filler(&self, c: char) -> bool {
+        !c.is_ascii_digit() && !matches!(c, '\n' | '\r') && c != self.blank && !self.is_symbol(c)
+    }
+}
+
+impl Solution for Day3 {
+    type Input = Schematic;
+    type Output = u64;
+
+    fn parse(&self, input: &str) -> SolutionResult<Schematic> {
+        Schematic::parse(input, &self.config)
+    }
+
+    fn part1(&self, input: &Schematic) -> SolutionResult<u64> {
+        first_case(&AdjacencyIndex::build(input, self.config.radius))
+    }
+
+    fn part2(&self, input: &Schematic) -> SolutionResult<u64> {
+        second_case(&AdjacencyIndex::build(input, self.config.radius))
+    }
+}
+
+/// A number in the schematic, occupying `col_start..=col_end` on `row`.
+/// Rows and columns are 0-based grid coordinates.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PartNumber {
+    pub value: u32,
+    pub row: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+}
+
+impl PartNumber {
+    /// Whether `(row, col)` lies within `radius` cells of one of this
+    /// number's digits, diagonals included, without being a digit itself.
+    pub fn touches(&self, (row, col): Position, radius: usize) -> bool {
+        let near_row = row + radius >= self.row && row <= self.row + radius;
+        let near_col = col + radius >= self.col_start && col <= self.col_end + radius;
+        let inside = row == self.row && (self.col_start..=self.col_end).contains(&col);
+        near_row && near_col && !inside
+    }
+
+    /// Every in-bounds cell this number touches at `radius`, in reading
+    /// order.
+    pub fn adjacent_cells(&self, grid: &Grid, radius: usize) -> Vec<Position> {
+        let rows = self.row.saturating_sub(radius)..=self.row + radius;
+        rows.flat_map(|row| {
+            let cols = self.col_start.saturating_sub(radius)..=self.col_end + radius;
+            cols.map(move |col| (row, col))
+        })
+        .filter(|&p| grid.contains(p) && self.touches(p, radius))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Symbol {
+    pub value: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Symbol {
+    pub fn position(&self) -> Position {
+        (self.row, self.col)
+    }
+}
+
+/// The parsed engine schematic: the raw grid plus every number and symbol
+/// located by grid coordinates.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub grid: Grid,
+    pub numbers: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str, config: &SchematicConfig) -> SolutionResult<Schematic> {
+        let grid = Grid::parse(input)?;
+        let spans = match combined_parser(config, Span::new(input)) {
+            Ok((_, (spans, _))) => spans,
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+                return Err(format!(
+                    "cannot parse schematic at {}:{}",
+                    e.input.location_line(),
+                    e.input.get_utf8_column()
+                )
+                .into())
+            }
+            Err(e) => return Err(format!("cannot parse schematic: {:?}", e).into()),
+        };
+
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        for span in spans {
+            let row = span.location_line() as usize - 1;
+            let col = span.get_utf8_column() - 1;
+            if span.fragment().starts_with(|c: char| c.is_ascii_digit()) {
+                numbers.push(PartNumber {
+                    value: span.fragment().parse::<u32>()?,
+                    row,
+                    col_start: col,
+                    col_end: col + span.fragment().len() - 1,
+                });
+            } else {
+                let found = span.fragment().chars().enumerate();
+                symbols.extend(
+                    found
+                        .filter(|&(_, c)| config.is_symbol(c))
+                        .map(|(i, value)| Symbol {
+                            value,
+                            row,
+                            col: col + i,
+                        }),
+                );
+            }
+        }
+
+        Ok(Schematic {
+            grid,
+            numbers,
+            symbols,
+        })
+    }
+}
+
+fn first_case(index: &AdjacencyIndex) -> SolutionResult<u64> {
+    index
+        .part_numbers()
+        .try_fold(0u64, |total, n| total.checked_add(u64::from(n.value)))
+        .ok_or_else(|| "the sum of part numbers overflows u64".into())
+}
+
+/// The product of the numbers around a gear. A gear touches exactly two, so
+/// this always fits.
+fn gear_ratio(numbers: &[&PartNumber]) -> u64 {
+    numbers.iter().map(|n| u64::from(n.value)).product()
+}
+
+fn second_case(index: &AdjacencyIndex) -> SolutionResult<u64> {
+    index
+        .symbols_with('*', 2)
+        .try_fold(0u64, |total, (_, numbers)| {
+            total.checked_add(gear_ratio(&numbers))
+        })
+        .ok_or_else(|| "the sum of gear ratios overflows u64".into())
+}
+
+fn blank_parser<'a>(config: &SchematicConfig, input: Span<'a>) -> IResult<Span<'a>, Span<'a>> {
+    take_while(|c| c == config.blank)(input)
+}
+
+fn number_parser<'a>(config: &SchematicConfig, input: Span<'a>) -> IResult<Span<'a>, Span<'a>> {
+    preceded(|i| blank_parser(config, i), digit1)(input)
+}
+
+fn symbol_parser<'a>(config: &SchematicConfig, input: Span<'a>) -> IResult<Span<'a>, Span<'a>> {
+    preceded(
+        |i| blank_parser(config, i),
+        take_while1(|c| config.is_symbol(c)),
+    )(input)
+}
+
+fn filler_parser<'a>(config: &SchematicConfig, input: Span<'a>) -> IResult<Span<'a>, Span<'a>> {
+    preceded(
+        |i| blank_parser(config, i),
+        take_while1(|c| config.is_filler(c)),
+    )(input)
+}
+
+fn newline_parser<'a>(config: &SchematicConfig, input: Span<'a>) -> IResult<Span<'a>, Span<'a>> {
+    preceded(|i| blank_parser(config, i), line_ending)(input)
+}
+
+fn combined_parser<'a>(
+    config: &SchematicConfig,
+    input: Span<'a>,
+) -> IResult<Span<'a>, (Vec<Span<'a>>, Span<'a>)> {
+    many_till(
+        alt((
+            |i| symbol_parser(config, i),
+            |i| number_parser(config, i),
+            |i| filler_parser(config, i),
+            |i| newline_parser(config, i),
+            |i| blank_parser(config, i),
+        )),
+        eof,
+    )(input)
+}
+
+#[cfg(test)]
+mod day3_tests {
+    use super::*;
+    use std::ops::Index;
+
+    #[test]
+    fn test_blank_parser() {
+        let result = blank_parser(&SchematicConfig::default(), Span::new("....343."));
+        let (rest, matched) = result.unwrap();
+        assert_eq!(rest.fragment(), &"343.");
+        assert_eq!(matched.fragment(), &"....");
+    }
+
+    #[test]
+    fn test_number_parser() {
+        let result = number_parser(&SchematicConfig::default(), Span::new("343...."));
+        let (rest, matched) = result.unwrap();
+        assert_eq!(rest.fragment(), &"....");
+        assert_eq!(matched.fragment(), &"343");
+    }
+
+    #[test]
+    fn test_symbol_parser() {
+        let result = symbol_parser(&SchematicConfig::default(), Span::new("%.."));
+        let (rest, matched) = result.unwrap();
+        assert_eq!(rest.fragment(), &"..");
+        assert_eq!(matched.fragment(), &"%");
+    }
+
+    fn parse(input: &str) -> Schematic {
+        Schematic::parse(input, &SchematicConfig::default()).unwrap()
+    }
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_first_case() {
+        assert_eq!(
+            first_case(&AdjacencyIndex::build(&parse(EXAMPLE), 1)).unwrap(),
+            4361
+        );
+    }
+
+    #[test]
+    fn test_second_case() {
+        assert_eq!(
+            second_case(&AdjacencyIndex::build(&parse(EXAMPLE), 1)).unwrap(),
+            467835
+        );
+    }
+
+    #[test]
+    fn test_second_case_gears_touching_long_numbers() {
+        let schematic = parse(
+            "\
+12345.....
+.....*....
+3.....6789
+.*........
+1.2.......",
+        );
+        // Only the first gear touches exactly two numbers; the second touches three.
+        assert_eq!(
+            second_case(&AdjacencyIndex::build(&schematic, 1)).unwrap(),
+            12345 * 6789
+        );
+    }
+
+    #[test]
+    fn test_second_case_ratios_beyond_u32() {
+        let schematic = parse("4000000000*4000000000\n.....................");
+        assert_eq!(
+            second_case(&AdjacencyIndex::build(&schematic, 1)).unwrap(),
+            16_000_000_000_000_000_000
+        );
+
+        let twice = "4000000000*4000000000\n.....................\n4000000000*4000000000";
+        let schematic = parse(twice);
+        assert!(second_case(&AdjacencyIndex::build(&schematic, 1)).is_err());
+    }
+
+    #[test]
+    fn test_first_case_numbers_at_row_edges() {
+        let schematic = parse("..12\n34..\n..#.");
+        assert_eq!(
+            first_case(&AdjacencyIndex::build(&schematic, 1)).unwrap(),
+            34
+        );
+    }
+
+    #[test]
+    fn test_first_case_sums_beyond_u32() {
+        let schematic = parse("4000000000*4000000000");
+        assert_eq!(
+            first_case(&AdjacencyIndex::build(&schematic, 1)).unwrap(),
+            8_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_default_policy_accepts_any_symbol() {
+        let schematic = parse("1~..2\n...é.\n3<.:4");
+        assert_eq!(
+            first_case(&AdjacencyIndex::build(&schematic, 1)).unwrap(),
+            1 + 2 + 3 + 4
+        );
+    }
+
+    #[test]
+    fn test_symbol_policy_can_be_restricted() {
+        let input = "1~..2\n...*.\n3<.:4";
+        let config = SchematicConfig {
+            blank: '.',
+            symbols: SymbolPolicy::OneOf(String::from("*")),
+            ..Default::default()
+        };
+        let schematic = Schematic::parse(input, &config).unwrap();
+        assert_eq!(
+            first_case(&AdjacencyIndex::build(&schematic, 1)).unwrap(),
+            2 + 4
+        );
+
+        let config = SchematicConfig {
+            blank: '.',
+            symbols: SymbolPolicy::Custom(|c| c == '<'),
+            ..Default::default()
+        };
+        let schematic = Schematic::parse(input, &config).unwrap();
+        assert_eq!(
+            first_case(&AdjacencyIndex::build(&schematic, 1)).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_blank_cell_is_configurable() {
+        let config = SchematicConfig {
+            blank: ' ',
+            ..Default::default()
+        };
+        let schematic = Schematic::parse("467  114  \n   *      \n  35  633 ", &config).unwrap();
+        assert_eq!(
+            first_case(&AdjacencyIndex::build(&schematic, 1)).unwrap(),
+            467 + 35
+        );
+    }
+
+    #[test]
+    fn test_adjacent_cells_stay_inside_the_grid() {
+        let schematic = parse("467..\n...*.\n..35.");
+        let [first, second] = schematic.numbers[..] else {
+            panic!("expected two numbers");
+        };
+
+        let cells = first.adjacent_cells(&schematic.grid, 1);
+        assert_eq!(cells, vec![(0, 3), (1, 0), (1, 1), (1, 2), (1, 3)]);
+
+        let cells = second.adjacent_cells(&schematic.grid, 1);
+        assert_eq!(cells, vec![(1, 1), (1, 2), (1, 3), (1, 4), (2, 1), (2, 4)]);
+
+        let cells = first.adjacent_cells(&schematic.grid, 2);
+        assert_eq!(cells.len(), 5 * 3 - 3);
+        assert!(cells.contains(&(2, 4)) && !cells.contains(&(0, 1)));
+        assert!(first.touches((2, 4), 2) && !first.touches((2, 4), 1));
+    }
+
+    #[test]
+    fn test_schematic_locates_numbers_by_row_and_column() {
+        let schematic = parse(EXAMPLE);
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(
+            schematic.numbers[4],
+            PartNumber {
+                value: 617,
+                row: 4,
+                col_start: 0,
+                col_end: 2
+            }
+        );
+        assert_eq!(
+            schematic.symbols[1],
+            Symbol {
+                value: '#',
+                row: 3,
+                col: 6
+            }
+        );
+        assert_eq!(schematic.symbols.len(), 6);
+    }
+
+    #[test]
+    fn test_schematic_reports_ragged_rows() {
+        let result = Schematic::parse("467..\n...*\n", &SchematicConfig::default());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "grid row 2 has 4 columns, expected 5"
+        );
+    }
+
+    #[test]
+    fn test_line_parser() {
+        let result = combined_parser(&SchematicConfig::default(), Span::new("..31%..#"));
+        let (_, (matches, _)) = result.unwrap();
+        let first_match = matches.index(0);
+        assert_eq!(matches.len(), 3);
+        assert_eq!(first_match.location_offset(), 2);
+        assert_eq!(first_match.fragment(), &"31")
+    }
+}
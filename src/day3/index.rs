@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use crate::grid::Position;
+
+use super::{PartNumber, Schematic, Symbol};
+
+/// Which numbers touch which symbols, built once from a parsed schematic.
+/// A number touches a symbol when the symbol lies within `radius` cells of
+/// any of its digits, diagonals included.
+#[derive(Debug, Clone)]
+pub struct AdjacencyIndex<'a> {
+    schematic: &'a Schematic,
+    radius: usize,
+    by_symbol: HashMap<Position, Vec<usize>>,
+    by_number: Vec<Vec<usize>>,
+}
+
+impl<'a> AdjacencyIndex<'a> {
+    pub fn build(schematic: &'a Schematic, radius: usize) -> AdjacencyIndex<'a> {
+        let symbol_at: HashMap<Position, usize> = schematic
+            .symbols
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.position(), i))
+            .collect();
+
+        let mut by_symbol: HashMap<Position, Vec<usize>> = HashMap::new();
+        let mut by_number = vec![vec![]; schematic.numbers.len()];
+
+        for (n, number) in schematic.numbers.iter().enumerate() {
+            for position in number.adjacent_cells(&schematic.grid, radius) {
+                if let Some(&s) = symbol_at.get(&position) {
+                    by_symbol.entry(position).or_default().push(n);
+                    by_number[n].push(s);
+                }
+            }
+        }
+
+        AdjacencyIndex {
+            schematic,
+            radius,
+            by_symbol,
+            by_number,
+        }
+    }
+
+    pub fn schematic(&self) -> &'a Schematic {
+        self.schematic
+    }
+
+    pub fn radius(&self) -> usize {
+        self.radius
+    }
+
+    /// The numbers touching the symbol at `position`, in reading order.
+    pub fn numbers_touching(&self, position: Position) -> Vec<&'a PartNumber> {
+        let schematic = self.schematic;
+        self.by_symbol
+            .get(&position)
+            .map(|ns| ns.iter().map(|&n| &schematic.numbers[n]).collect())
+            .unwrap_or_default()
+    }
+
+    /// The symbols touching `number`, in reading order.
+    pub fn symbols_touching(&self, number: &PartNumber) -> Vec<&'a Symbol> {
+        let schematic = self.schematic;
+        schematic
+            .numbers
+            .iter()
+            .position(|n| n == number)
+            .map(|n| {
+                self.by_number[n]
+                    .iter()
+                    .map(|&s| &schematic.symbols[s])
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Numbers touching at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &'a PartNumber> + '_ {
+        let schematic = self.schematic;
+        self.by_number
+            .iter()
+            .enumerate()
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(move |(n, _)| &schematic.numbers[n])
+    }
+
+    /// Symbols drawn as `value` that touch exactly `count` numbers, together
+    /// with those numbers.
+    pub fn symbols_with(
+        &self,
+        value: char,
+        count: usize,
+    ) -> impl Iterator<Item = (&'a Symbol, Vec<&'a PartNumber>)> + '_ {
+        self.schematic
+            .symbols
+            .iter()
+            .filter(move |s| s.value == value)
+            .map(|s| (s, self.numbers_touching(s.position())))
+            .filter(move |(_, numbers)| numbers.len() == count)
+    }
+}
+
+#[cfg(test)]
+mod index_tests {
+    use super::super::SchematicConfig;
+    use super::*;
+
+    fn parse(input: &str) -> Schematic {
+        Schematic::parse(input, &SchematicConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_numbers_and_symbols_touching() {
+        let schematic = parse("467..114..\n...*......\n..35..633.");
+        let index = AdjacencyIndex::build(&schematic, 1);
+
+        let values: Vec<u32> = index
+            .numbers_touching((1, 3))
+            .iter()
+            .map(|n| n.value)
+            .collect();
+        assert_eq!(values, vec![467, 35]);
+        assert!(index.numbers_touching((0, 0)).is_empty());
+
+        let symbols = index.symbols_touching(&schematic.numbers[0]);
+        assert_eq!(symbols, vec![&schematic.symbols[0]]);
+        assert!(index.symbols_touching(&schematic.numbers[1]).is_empty());
+    }
+
+    #[test]
+    fn test_radius_widens_the_neighbourhood() {
+        let schematic = parse("1....\n.....\n..#..");
+        let near = AdjacencyIndex::build(&schematic, 1);
+        let far = AdjacencyIndex::build(&schematic, 2);
+
+        assert_eq!(near.part_numbers().count(), 0);
+        assert_eq!(far.part_numbers().count(), 1);
+        assert_eq!(far.radius(), 2);
+    }
+
+    #[test]
+    fn test_symbols_with() {
+        let schematic = parse("1*2\n.*.\n...");
+        let index = AdjacencyIndex::build(&schematic, 1);
+
+        let pairs: Vec<Position> = index
+            .symbols_with('*', 2)
+            .map(|(s, _)| s.position())
+            .collect();
+        assert_eq!(pairs, vec![(0, 1), (1, 1)]);
+        assert_eq!(index.symbols_with('#', 2).count(), 0);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::grid::Position;
+
+use super::{gear_ratio, AdjacencyIndex, PartNumber, Symbol};
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Style {
+    Part,
+    Loose,
+    Gear,
+}
+
+impl Style {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Style::Part => "\x1b[32m",
+            Style::Loose => "\x1b[31m",
+            Style::Gear => "\x1b[1;33m",
+        }
+    }
+
+    fn class(&self) -> &'static str {
+        match self {
+            Style::Part => "part",
+            Style::Loose => "loose",
+            Style::Gear => "gear",
+        }
+    }
+}
+
+/// What starts at a cell: a number or a gear, with the style to draw it in.
+enum Mark<'a> {
+    Number(&'a PartNumber, Style),
+    Gear(&'a Symbol),
+}
+
+fn marks<'a>(index: &AdjacencyIndex<'a>) -> HashMap<Position, Mark<'a>> {
+    let schematic = index.schematic();
+    let mut marks: HashMap<Position, Mark> = schematic
+        .numbers
+        .iter()
+        .map(|n| {
+            let style = if index.symbols_touching(n).is_empty() {
+                Style::Loose
+            } else {
+                Style::Part
+            };
+            ((n.row, n.col_start), Mark::Number(n, style))
+        })
+        .collect();
+
+    for (gear, _) in index.symbols_with('*', 2) {
+        marks.insert(gear.position(), Mark::Gear(gear));
+    }
+    marks
+}
+
+/// The schematic with part numbers in green, numbers touching no symbol in
+/// red and gears in bold yellow, using ANSI escape codes.
+pub fn render_ansi(index: &AdjacencyIndex) -> String {
+    let grid = &index.schematic().grid;
+    let marks = marks(index);
+    let mut out = String::new();
+
+    for (row, cells) in grid.rows().enumerate() {
+        let mut col = 0;
+        while col < cells.len() {
+            match marks.get(&(row, col)) {
+                Some(Mark::Number(n, style)) => {
+                    let text: String = cells[n.col_start..=n.col_end].iter().collect();
+                    out.push_str(style.ansi());
+                    out.push_str(&text);
+                    out.push_str(RESET);
+                    col = n.col_end + 1;
+                }
+                Some(Mark::Gear(g)) => {
+                    out.push_str(Style::Gear.ansi());
+                    out.push(g.value);
+                    out.push_str(RESET);
+                    col += 1;
+                }
+                None => {
+                    out.push(cells[col]);
+                    col += 1;
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// A self-contained HTML page of the schematic. Hovering a number shows its
+/// row, columns and the symbols it touches; hovering a gear shows its ratio.
+/// Coordinates in tooltips are 1-based, like `line:column` in parse errors.
+pub fn render_html(index: &AdjacencyIndex) -> String {
+    let grid = &index.schematic().grid;
+    let marks = marks(index);
+    let mut out = String::from(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Engine schematic</title>
+<style>
+body { background: #111; color: #777; }
+pre { font-size: 14px; line-height: 1.2; }
+.part { color: #3c3; }
+.loose { color: #e44; }
+.gear { color: #fd3; font-weight: bold; }
+span[title]:hover { background: #444; }
+</style>
+</head>
+<body>
+<pre>",
+    );
+
+    for (row, cells) in grid.rows().enumerate() {
+        let mut col = 0;
+        while col < cells.len() {
+            match marks.get(&(row, col)) {
+                Some(Mark::Number(n, style)) => {
+                    let text: String = cells[n.col_start..=n.col_end].iter().collect();
+                    let _ = write!(
+                        out,
+                        "<span class=\"{}\" title=\"{}\">{}</span>",
+                        style.class(),
+                        escape(&number_tooltip(index, n)),
+                        escape(&text)
+                    );
+                    col = n.col_end + 1;
+                }
+                Some(Mark::Gear(g)) => {
+                    let ratio = gear_ratio(&index.numbers_touching(g.position()));
+                    let _ = write!(
+                        out,
+                        "<span class=\"gear\" title=\"gear at {}:{}, ratio {}\">{}</span>",
+                        g.row + 1,
+                        g.col + 1,
+                        ratio,
+                        escape(&g.value.to_string())
+                    );
+                    col += 1;
+                }
+                None => {
+                    out.push_str(&escape(&cells[col].to_string()));
+                    col += 1;
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn number_tooltip(index: &AdjacencyIndex, number: &PartNumber) -> String {
+    let symbols: Vec<String> = index
+        .symbols_touching(number)
+        .iter()
+        .map(|s| format!("{} at {}:{}", s.value, s.row + 1, s.col + 1))
+        .collect();
+    let touching = if symbols.is_empty() {
+        String::from("no symbols")
+    } else {
+        symbols.join(", ")
+    };
+
+    format!(
+        "{}: row {}, columns {}-{}; touches {}",
+        number.value,
+        number.row + 1,
+        number.col_start + 1,
+        number.col_end + 1,
+        touching
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod render_tests {
+    use super::super::{Schematic, SchematicConfig};
+    use super::*;
+
+    const INPUT: &str = "467..114..\n...*......\n..35..<...";
+
+    fn schematic() -> Schematic {
+        Schematic::parse(INPUT, &SchematicConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let schematic = schematic();
+        let index = AdjacencyIndex::build(&schematic, 1);
+        let rendered = render_ansi(&index);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "\x1b[32m467\x1b[0m..\x1b[31m114\x1b[0m..");
+        assert_eq!(lines[1], "...\x1b[1;33m*\x1b[0m......");
+        assert_eq!(lines[2], "..\x1b[32m35\x1b[0m..<...");
+    }
+
+    #[test]
+    fn test_render_html() {
+        let schematic = schematic();
+        let index = AdjacencyIndex::build(&schematic, 1);
+        let rendered = render_html(&index);
+
+        assert!(rendered.starts_with("<!DOCTYPE html>"));
+        assert!(rendered.contains(
+            "<span class=\"part\" title=\"467: row 1, columns 1-3; touches * at 2:4\">467</span>"
+        ));
+        assert!(rendered.contains(
+            "<span class=\"loose\" title=\"114: row 1, columns 6-8; touches no symbols\">114</span>"
+        ));
+        assert!(
+            rendered.contains("<span class=\"gear\" title=\"gear at 2:4, ratio 16345\">*</span>")
+        );
+        assert!(rendered.contains("..&lt;..."));
+    }
+
+    #[test]
+    fn test_render_html_ratios_beyond_u32() {
+        let input = "4000000000*4000000000";
+        let schematic = Schematic::parse(input, &SchematicConfig::default()).unwrap();
+        let rendered = render_html(&AdjacencyIndex::build(&schematic, 1));
+        assert!(rendered.contains("ratio 16000000000000000000"));
+    }
+}
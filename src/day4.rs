@@ -0,0 +1,159 @@
+use nom::bytes::complete::{tag, take_until};
+use nom::character::complete::{digit1, space0};
+use nom::multi::many0;
+use nom::sequence::{preceded, separated_pair, terminated};
+use nom::IResult;
+
+use crate::solution::{Solution, SolutionResult};
+
+pub struct Day4;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Card {
+    numbers: Vec<u32>,
+    winners: Vec<u32>,
+}
+
+impl Card {
+    fn matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|n| self.winners.contains(n))
+            .count()
+    }
+}
+
+impl Solution for Day4 {
+    type Input = Vec<Card>;
+    type Output = u32;
+
+    fn parse(&self, input: &str) -> SolutionResult<Vec<Card>> {
+        input.lines().map(parse_card).collect()
+    }
+
+    fn part1(&self, input: &Vec<Card>) -> SolutionResult<u32> {
+        first_case(input)
+    }
+
+    fn part2(&self, input: &Vec<Card>) -> SolutionResult<u32> {
+        Ok(second_case(input))
+    }
+}
+
+fn parse_card(line: &str) -> SolutionResult<Card> {
+    let (numbers, winners) = match line_parser(line) {
+        Ok((_, parsed)) => parsed,
+        Err(e) => return Err(format!("cannot parse card '{}': {:?}", line, e).into()),
+    };
+    let values = |numbers: Vec<&str>| -> SolutionResult<Vec<u32>> {
+        numbers
+            .into_iter()
+            .map(|n| {
+                n.parse()
+                    .map_err(|e| format!("invalid number '{}' in card '{}': {}", n, line, e).into())
+            })
+            .collect()
+    };
+
+    Ok(Card {
+        numbers: values(numbers)?,
+        winners: values(winners)?,
+    })
+}
+
+fn first_case(input: &[Card]) -> SolutionResult<u32> {
+    input.iter().try_fold(0u32, |total, card| {
+        let points = match card.matches() {
+            0 => 0,
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|power| 2u32.checked_pow(power))
+                .ok_or_else(|| {
+                    format!("a card with {} matches is worth too many points", matches)
+                })?,
+        };
+        total
+            .checked_add(points)
+            .ok_or_else(|| "the sum of card points overflows u32".into())
+    })
+}
+
+fn second_case(input: &[Card]) -> u32 {
+    let mut scratch_card_counts: Vec<u32> = vec![1; input.len()];
+
+    for (index, card) in input.iter().enumerate() {
+        let matches = card.matches();
+        let parent_card_count = scratch_card_counts[index];
+        for current_count in scratch_card_counts.iter_mut().skip(index + 1).take(matches) {
+            *current_count += parent_card_count;
+        }
+    }
+
+    scratch_card_counts.into_iter().sum()
+}
+
+fn line_parser(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+    preceded(
+        terminated(take_until(":"), tag(":")),
+        separated_pair(numbers_parser, tag("|"), numbers_parser),
+    )(input)
+}
+
+fn numbers_parser(input: &str) -> IResult<&str, Vec<&str>> {
+    many0(preceded(space0, terminated(digit1, space0)))(input)
+}
+
+#[cfg(test)]
+mod day4_tests {
+    use super::*;
+
+    #[test]
+    fn test_line_parser() {
+        let result = line_parser("Card 1: 43 45 | 12 45");
+        assert_eq!(result, Ok(("", (vec!["43", "45"], vec!["12", "45"]))))
+    }
+
+    #[test]
+    fn test_second_case() {
+        let input = Day4
+            .parse(
+                "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+            )
+            .unwrap();
+
+        let result = second_case(&input);
+        assert_eq!(result, 30)
+    }
+
+    #[test]
+    fn test_parse_reports_numbers_out_of_range() {
+        let error = Day4.parse("Card 1: 4294967296 | 1").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid number '4294967296' in card 'Card 1: 4294967296 | 1': \
+             number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn test_first_case_reports_overflowing_points() {
+        let card = |matches: u32| Card {
+            numbers: (0..matches).collect(),
+            winners: (0..matches).collect(),
+        };
+        assert_eq!(first_case(&[card(32)]).unwrap(), 1 << 31);
+        assert_eq!(
+            first_case(&[card(33)]).unwrap_err().to_string(),
+            "a card with 33 matches is worth too many points"
+        );
+        assert_eq!(
+            first_case(&[card(32), card(32)]).unwrap_err().to_string(),
+            "the sum of card points overflows u32"
+        );
+    }
+}
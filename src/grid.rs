@@ -0,0 +1,229 @@
+use crate::input::InputError;
+
+pub type Position = (usize, usize);
+
+const OFFSETS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular block of cells, stored row-major, addressed as
+/// `(row, col)` from the top-left corner.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T = char> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid<char> {
+    /// One row per line. Blank lines at the end of `input` are not rows.
+    pub fn parse(input: &str) -> Result<Grid<char>, InputError> {
+        let input = input.trim_end_matches(['\n', '\r']);
+        Grid::from_rows(input.lines().map(|line| line.chars().collect()))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Grid<T>, InputError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for (index, row) in rows.into_iter().enumerate() {
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(InputError::RaggedGrid {
+                    row: index + 1,
+                    expected,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+            height += 1;
+        }
+
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if self.contains((row, col)) {
+            Some(&self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `position` by `(d_row, d_col)`, or returns `None` if that would
+    /// leave the grid. Never wraps around an edge.
+    pub fn offset(&self, (row, col): Position, (d_row, d_col): (isize, isize)) -> Option<Position> {
+        let target = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        if self.contains(target) {
+            Some(target)
+        } else {
+            None
+        }
+    }
+
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS4
+            .iter()
+            .filter_map(move |&d| self.offset(position, d))
+    }
+
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS8
+            .iter()
+            .filter_map(move |&d| self.offset(position, d))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row < self.height {
+            Some(&self.cells[row * self.width..(row + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        if col < self.width {
+            Some(self.cells.iter().skip(col).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |r| &self.cells[r * self.width..(r + 1) * self.width])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |c| self.cells.iter().skip(c).step_by(self.width))
+    }
+
+    /// Every cell together with its position, in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_detects_dimensions() {
+        let grid = Grid::parse("467..\n...*.\n..35.\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(grid.get(1, 3), Some(&'*'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 5), None);
+
+        let trailing = Grid::parse("467..\r\n...*.\r\n\r\n\n").unwrap();
+        assert_eq!(trailing, Grid::parse("467..\n...*.").unwrap());
+    }
+
+    #[test]
+    fn test_parse_rejects_ragged_rows() {
+        let result = Grid::parse("467..\n...*\n..35.");
+        assert!(matches!(
+            result,
+            Err(InputError::RaggedGrid {
+                row: 2,
+                expected: 5,
+                found: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse("ab\ncd\nef").unwrap();
+        assert_eq!(grid.row(1), Some(&['c', 'd'][..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(grid.column(1).unwrap().collect::<String>(), "bdf");
+        assert!(grid.column(2).is_none());
+        assert_eq!(grid.rows().count(), 3);
+
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, vec!["ace", "bdf"]);
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid = Grid::parse("").unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.neighbors8((0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_neighbors_at_corners_and_edges() {
+        let grid = Grid::parse("abc\ndef\nghi").unwrap();
+
+        let corner: Vec<Position> = grid.neighbors8((0, 0)).collect();
+        assert_eq!(corner, vec![(0, 1), (1, 0), (1, 1)]);
+
+        let edge: Vec<Position> = grid.neighbors4((1, 2)).collect();
+        assert_eq!(edge, vec![(0, 2), (1, 1), (2, 2)]);
+
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors4((2, 2)).count(), 2);
+    }
+
+    #[test]
+    fn test_offset_does_not_wrap() {
+        let grid = Grid::parse("abc\ndef").unwrap();
+        assert_eq!(grid.offset((1, 0), (0, -1)), None);
+        assert_eq!(grid.offset((0, 2), (0, 1)), None);
+        assert_eq!(grid.offset((0, 2), (1, -1)), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_cells_and_map() {
+        let grid = Grid::parse("1.\n.2").unwrap();
+        let digits = grid.map(|c| c.to_digit(10));
+        let found: Vec<(Position, u32)> = digits
+            .cells()
+            .filter_map(|(p, d)| d.map(|d| (p, d)))
+            .collect();
+        assert_eq!(found, vec![((0, 0), 1), ((1, 1), 2)]);
+    }
+}
@@ -0,0 +1,135 @@
+//! Probabilities of drawing without replacement. Everything is computed in
+//! log space so large populations neither overflow nor underflow; the
+//! plain versions just exponentiate.
+
+use std::f64::consts::PI;
+
+/// Below this many factors `ln_choose` multiplies them out; from here on the
+/// Stirling series in `ln_factorial` is accurate to well under `1e-15`.
+const DIRECT: u64 = 64;
+
+/// `ln C(n, k)`, or negative infinity when `k > n`. Takes `u64` so that
+/// populations summed from `u32` counts fit, and runs in constant time
+/// however large they are.
+pub fn ln_choose(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    if k >= DIRECT {
+        return ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k);
+    }
+    (0..k)
+        .map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln())
+        .sum()
+}
+
+/// `ln n!` from the Stirling series, for `n >= DIRECT`.
+fn ln_factorial(n: u64) -> f64 {
+    let x = n as f64;
+    x * x.ln() - x + 0.5 * (2.0 * PI * x).ln() + 1.0 / (12.0 * x) - 1.0 / (360.0 * x.powi(3))
+        + 1.0 / (1260.0 * x.powi(5))
+}
+
+pub fn choose(n: u64, k: u64) -> f64 {
+    ln_choose(n, k).exp()
+}
+
+/// `ln` of the chance that `draws` cubes taken from `population`, of which
+/// `successes` are marked, include exactly `observed` marked ones.
+pub fn ln_hypergeometric(population: u32, successes: u32, draws: u32, observed: u32) -> f64 {
+    if successes > population || draws > population || observed > draws {
+        return f64::NEG_INFINITY;
+    }
+    let choose = |n: u32, k: u32| ln_choose(u64::from(n), u64::from(k));
+    choose(successes, observed) + choose(population - successes, draws - observed)
+        - choose(population, draws)
+}
+
+pub fn hypergeometric(population: u32, successes: u32, draws: u32, observed: u32) -> f64 {
+    ln_hypergeometric(population, successes, draws, observed).exp()
+}
+
+/// `ln` of the chance that drawing `drawn.iter().sum()` cubes from a bag
+/// holding `bag[i]` cubes of each kind `i` gives exactly `drawn[i]` of each.
+/// Kinds missing from the end of either slice count as 0. The totals are
+/// summed as `u64`, so they cannot overflow.
+pub fn ln_multivariate_hypergeometric(bag: &[u32], drawn: &[u32]) -> f64 {
+    let kinds = bag.len().max(drawn.len());
+    let at = |counts: &[u32], i: usize| u64::from(counts.get(i).copied().unwrap_or(0));
+
+    let population: u64 = bag.iter().copied().map(u64::from).sum();
+    let draws: u64 = drawn.iter().copied().map(u64::from).sum();
+    let ways: f64 = (0..kinds)
+        .map(|i| ln_choose(at(bag, i), at(drawn, i)))
+        .sum();
+    ways - ln_choose(population, draws)
+}
+
+pub fn multivariate_hypergeometric(bag: &[u32], drawn: &[u32]) -> f64 {
+    ln_multivariate_hypergeometric(bag, drawn).exp()
+}
+
+#[cfg(test)]
+mod hypergeometric_tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_choose() {
+        assert!(close(choose(5, 2), 10.0));
+        assert!(close(choose(5, 0), 1.0));
+        assert!(close(choose(52, 5), 2_598_960.0));
+        assert_eq!(choose(3, 4), 0.0);
+    }
+
+    #[test]
+    fn test_ln_choose_of_large_populations() {
+        // Straddling `DIRECT`, both ways of computing must agree.
+        let direct: f64 = (0..70)
+            .map(|i| (f64::from(200 - i) / f64::from(i + 1)).ln())
+            .sum();
+        assert!(close(ln_choose(200, 70), direct));
+        assert!(close(ln_choose(200, 63), ln_choose(200, 137)));
+
+        assert!(close(ln_choose(1_000_000, 300_000), 610857.2556846421));
+        assert!(close(ln_choose(100_000_000, 50_000_000), 69314708.61986303));
+    }
+
+    #[test]
+    fn test_hypergeometric() {
+        // One of three draws from ten cubes, four of them red.
+        assert!(close(hypergeometric(10, 4, 3, 1), 0.5));
+        let total: f64 = (0..=3).map(|k| hypergeometric(10, 4, 3, k)).sum();
+        assert!(close(total, 1.0));
+        assert_eq!(hypergeometric(10, 2, 3, 3), 0.0);
+        assert_eq!(hypergeometric(2, 1, 3, 1), 0.0);
+    }
+
+    #[test]
+    fn test_multivariate_hypergeometric() {
+        assert!(close(
+            multivariate_hypergeometric(&[2, 3, 5], &[1, 1, 1]),
+            0.25
+        ));
+        assert!(close(
+            multivariate_hypergeometric(&[4, 6], &[1, 2]),
+            hypergeometric(10, 4, 3, 1)
+        ));
+        assert!(close(multivariate_hypergeometric(&[3], &[3]), 1.0));
+        assert_eq!(multivariate_hypergeometric(&[3, 0], &[1, 1]), 0.0);
+
+        let huge = 4_000_000_000;
+        assert!(close(
+            multivariate_hypergeometric(&[huge, huge], &[huge, huge]),
+            1.0
+        ));
+        assert_eq!(
+            multivariate_hypergeometric(&[huge, huge, 0], &[1, 1, 1]),
+            0.0
+        );
+    }
+}
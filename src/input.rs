@@ -0,0 +1,199 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+use crate::grid::Grid;
+
+#[derive(Debug)]
+pub enum InputError {
+    /// A required command line argument (e.g. `--input`) was not given.
+    MissingArgument(&'static str),
+    Unreadable {
+        path: PathBuf,
+        source: io::Error,
+    },
+    InvalidUtf8 {
+        path: PathBuf,
+        valid_up_to: usize,
+    },
+    /// A grid row (1-based) whose length differs from the first row's.
+    RaggedGrid {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::MissingArgument(name) => write!(f, "missing required argument {}", name),
+            InputError::Unreadable { path, source } => {
+                write!(f, "cannot read {}: {}", path.display(), source)
+            }
+            InputError::InvalidUtf8 { path, valid_up_to } => write!(
+                f,
+                "{} is not valid UTF-8 (first bad byte at offset {})",
+                path.display(),
+                valid_up_to
+            ),
+            InputError::RaggedGrid {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "grid row {} has {} columns, expected {}",
+                row, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Unreadable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Where puzzle input comes from: `-` (or no argument at all) means stdin.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    pub fn from_arg(arg: Option<&str>) -> Source {
+        match arg {
+            None | Some("-") => Source::Stdin,
+            Some(path) => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Source::Stdin => read_stdin(),
+            Source::File(path) => read_input(path),
+        }
+    }
+}
+
+pub fn required<T>(value: Option<T>, name: &'static str) -> Result<T, InputError> {
+    value.ok_or(InputError::MissingArgument(name))
+}
+
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).map_err(|source| InputError::Unreadable {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    decode(path.to_path_buf(), bytes)
+}
+
+pub fn read_stdin() -> Result<String, InputError> {
+    let path = PathBuf::from("<stdin>");
+    let mut stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Err(InputError::MissingArgument("--input (or piped stdin)"));
+    }
+
+    let mut bytes = Vec::new();
+    stdin
+        .read_to_end(&mut bytes)
+        .map_err(|source| InputError::Unreadable {
+            path: path.clone(),
+            source,
+        })?;
+    decode(path, bytes)
+}
+
+fn decode(path: PathBuf, bytes: Vec<u8>) -> Result<String, InputError> {
+    String::from_utf8(bytes).map_err(|e| InputError::InvalidUtf8 {
+        path,
+        valid_up_to: e.utf8_error().valid_up_to(),
+    })
+}
+
+pub fn read_lines(path: impl AsRef<Path>) -> Result<Vec<String>, InputError> {
+    Ok(read_input(path)?.lines().map(String::from).collect())
+}
+
+pub fn read_grid(path: impl AsRef<Path>) -> Result<Grid, InputError> {
+    Grid::parse(&read_input(path)?)
+}
+
+#[cfg(test)]
+mod input_tests {
+    use super::*;
+    use std::env;
+
+    fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-input-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_lines() {
+        let path = temp_file("lines", b"1abc2\ntreb7uchet\n");
+        assert_eq!(read_lines(&path).unwrap(), vec!["1abc2", "treb7uchet"]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_grid() {
+        let path = temp_file("grid", b"467..\n...*.\n");
+        let grid = read_grid(&path).unwrap();
+        assert_eq!((grid.width(), grid.height()), (5, 2));
+        fs::remove_file(path).unwrap();
+
+        let path = temp_file("ragged", b"467..\n...*\n");
+        let error = read_grid(&path).unwrap_err();
+        assert_eq!(error.to_string(), "grid row 2 has 4 columns, expected 5");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let result = read_input("/definitely/not/here.txt");
+        assert!(matches!(result, Err(InputError::Unreadable { .. })));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let path = temp_file("utf8", b"ab\xffcd");
+        let result = read_input(&path);
+        assert!(matches!(
+            result,
+            Err(InputError::InvalidUtf8 { valid_up_to: 2, .. })
+        ));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_source_from_arg() {
+        assert_eq!(Source::from_arg(None), Source::Stdin);
+        assert_eq!(Source::from_arg(Some("-")), Source::Stdin);
+        assert_eq!(
+            Source::from_arg(Some("day1.txt")),
+            Source::File(PathBuf::from("day1.txt"))
+        );
+    }
+
+    #[test]
+    fn test_required() {
+        let result = required(None::<String>, "--input");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "missing required argument --input"
+        );
+    }
+}
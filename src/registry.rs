@@ -0,0 +1,134 @@
+use crate::day1::Day1;
+use crate::day2::Day2;
+use crate::day3::Day3;
+use crate::day4::Day4;
+use crate::solution::{Options, Runner, SolutionResult};
+
+/// A registered puzzle day, how many of its parts are solved, which command
+/// line options it accepts and how to build its solution from them.
+pub struct Day {
+    pub day: u32,
+    pub parts: usize,
+    pub options: &'static [&'static str],
+    pub solution: fn(&Options) -> SolutionResult<Box<dyn Runner>>,
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        parts: 2,
+        options: Day1::OPTIONS,
+        solution: |options| Ok(Box::new(Day1::from_options(options)?)),
+    },
+    Day {
+        day: 2,
+        parts: 2,
+        options: Day2::OPTIONS,
+        solution: |options| Ok(Box::new(Day2::from_options(options)?)),
+    },
+    Day {
+        day: 3,
+        parts: 2,
+        options: &[],
+        solution: |_| Ok(Box::new(Day3::default())),
+    },
+    Day {
+        day: 4,
+        parts: 2,
+        options: &[],
+        solution: |_| Ok(Box::new(Day4)),
+    },
+];
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+/// Solves one part of a registered day straight from an in-memory input.
+pub fn solve(day: u32, part: usize, input: &str) -> SolutionResult<String> {
+    let day = find(day).ok_or_else(|| format!("day {} is not registered", day))?;
+    let report = (day.solution)(&Options::default())?.run(input, &[part])?;
+    report
+        .answers
+        .into_iter()
+        .next()
+        .ok_or("no answer was produced")?
+        .result
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(2).map(|d| d.day), Some(2));
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        assert_eq!(solve(1, 1, input).unwrap(), "142");
+        assert!(solve(1, 3, input).is_err());
+        assert!(solve(25, 1, input).is_err());
+    }
+
+    const DAY1_PART2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    const DAY2: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    const DAY3: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    const DAY4: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    /// The published example of every part, with its published answer.
+    const EXAMPLES: &[(u32, usize, &str, &str)] = &[
+        (1, 1, "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet", "142"),
+        (1, 2, DAY1_PART2, "281"),
+        (2, 1, DAY2, "8"),
+        (2, 2, DAY2, "2286"),
+        (3, 1, DAY3, "4361"),
+        (3, 2, DAY3, "467835"),
+        (4, 1, DAY4, "13"),
+        (4, 2, DAY4, "30"),
+    ];
+
+    #[test]
+    fn test_registered_days_solve_the_examples() {
+        for day in DAYS {
+            for part in 1..=day.parts {
+                let (_, _, input, answer) = EXAMPLES
+                    .iter()
+                    .find(|&&(d, p, _, _)| d == day.day && p == part)
+                    .unwrap_or_else(|| panic!("no example for day {} part {}", day.day, part));
+                let result = solve(day.day, part, input);
+                assert_eq!(result.unwrap(), *answer, "day {} part {}", day.day, part);
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+pub type SolutionResult<T> = Result<T, Box<dyn Error>>;
+
+/// A puzzle day: the raw input is parsed once into `Input` and both parts
+/// work off that shared model.
+pub trait Solution {
+    type Input;
+    type Output: Display;
+
+    fn parse(&self, input: &str) -> SolutionResult<Self::Input>;
+
+    /// Like `parse`, but also describes each line that was passed over
+    /// instead of failing the parse. Days that never skip keep the default.
+    fn parse_with_skipped(&self, input: &str) -> SolutionResult<(Self::Input, Vec<String>)> {
+        Ok((self.parse(input)?, vec![]))
+    }
+
+    fn part1(&self, input: &Self::Input) -> SolutionResult<Self::Output>;
+
+    fn part2(&self, _input: &Self::Input) -> SolutionResult<Self::Output> {
+        Err("part 2 is not solved yet".into())
+    }
+}
+
+/// Day-specific settings, passed on the command line as `--name value`.
+/// Each day declares the names it understands; `check` rejects the rest.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    values: HashMap<String, String>,
+}
+
+impl Options {
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Fails on the first name, alphabetically, that is not in `known`.
+    pub fn check(&self, known: &[&str]) -> SolutionResult<()> {
+        let mut names: Vec<&String> = self.values.keys().collect();
+        names.sort();
+        match names
+            .into_iter()
+            .find(|name| !known.contains(&name.as_str()))
+        {
+            Some(name) => Err(format!("unknown option --{}", name).into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Parses the value of `name` if it was given.
+    pub fn parse<T: std::str::FromStr>(&self, name: &str) -> SolutionResult<Option<T>>
+    where
+        T::Err: Display,
+    {
+        self.get(name)
+            .map(|value| {
+                value
+                    .parse::<T>()
+                    .map_err(|e| format!("invalid --{} '{}': {}", name, value, e).into())
+            })
+            .transpose()
+    }
+}
+
+#[derive(Debug)]
+pub struct Answer {
+    pub part: usize,
+    pub result: SolutionResult<String>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub parse_elapsed: Duration,
+    /// What `parse_with_skipped` passed over, one entry per line.
+    pub skipped: Vec<String>,
+    pub answers: Vec<Answer>,
+}
+
+/// Object-safe view of a [`Solution`] so days with different input models
+/// can sit side by side in the registry.
+pub trait Runner {
+    fn run(&self, input: &str, parts: &[usize]) -> SolutionResult<Report>;
+}
+
+impl<S: Solution> Runner for S {
+    fn run(&self, input: &str, parts: &[usize]) -> SolutionResult<Report> {
+        let start = Instant::now();
+        let (parsed, skipped) = self.parse_with_skipped(input)?;
+        let parse_elapsed = start.elapsed();
+
+        let answers = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let result = match part {
+                    1 => self.part1(&parsed),
+                    2 => self.part2(&parsed),
+                    _ => Err(format!("there is no part {}", part).into()),
+                };
+                Answer {
+                    part,
+                    result: result.map(|output| output.to_string()),
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect();
+
+        Ok(Report {
+            parse_elapsed,
+            skipped,
+            answers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod solution_tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Output = u32;
+
+        fn parse(&self, input: &str) -> SolutionResult<Vec<u32>> {
+            Ok(input
+                .split_whitespace()
+                .map(|n| n.parse::<u32>())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part1(&self, input: &Vec<u32>) -> SolutionResult<u32> {
+            Ok(input.iter().sum())
+        }
+    }
+
+    #[test]
+    fn test_runner_parses_once_for_all_parts() {
+        let report = Sum.run("1 2 3", &[1, 2, 3]).unwrap();
+        let results: Vec<Option<String>> =
+            report.answers.into_iter().map(|a| a.result.ok()).collect();
+        assert_eq!(results, vec![Some(String::from("6")), None, None]);
+    }
+
+    #[test]
+    fn test_options() {
+        let mut options = Options::default();
+        options.set("radius", "2");
+        options.set("mode", "x");
+
+        assert_eq!(options.get("mode"), Some("x"));
+        assert_eq!(options.parse::<usize>("radius").unwrap(), Some(2));
+        assert_eq!(options.parse::<usize>("missing").unwrap(), None);
+        assert_eq!(
+            options.parse::<usize>("mode").unwrap_err().to_string(),
+            "invalid --mode 'x': invalid digit found in string"
+        );
+
+        assert!(options.check(&["mode", "radius"]).is_ok());
+        assert_eq!(
+            options.check(&["mode"]).unwrap_err().to_string(),
+            "unknown option --radius"
+        );
+    }
+
+    #[test]
+    fn test_runner_reports_parse_errors() {
+        assert!(Sum.run("1 x", &[1]).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! Helpers shared by the unit tests and the benches. Compiled only for
+//! tests here; the benches include this file with `#[path]`.
+
+/// A deterministic xorshift generator, so that generated inputs are the same
+/// on every run.
+#[derive(Debug, Clone)]
+pub struct XorShift(u64);
+
+impl XorShift {
+    /// `seed` must not be 0, which xorshift never leaves.
+    pub fn new(seed: u64) -> XorShift {
+        assert_ne!(seed, 0, "xorshift needs a non-zero seed");
+        XorShift(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod testing_tests {
+    use super::*;
+
+    #[test]
+    fn test_xorshift_is_deterministic() {
+        let mut a = XorShift::new(1);
+        let mut b = XorShift::new(1);
+        let first: Vec<u64> = (0..3).map(|_| a.next_u64()).collect();
+        assert_eq!(
+            first,
+            vec![1082269761, 1152992998833853505, 11177516664432764457]
+        );
+        assert_eq!(b.next_u64(), first[0]);
+        assert!((0..100).all(|_| a.below(7) < 7));
+    }
+}